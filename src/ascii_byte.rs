@@ -1,6 +1,16 @@
+use std::fmt;
 use std::io;
+use std::marker::PhantomData;
 
-use io::ErrorKind;
+use crate::ascii_bytes::{AsciiBytesToBool, AsciiBytesToBoolPair};
+use crate::error::BoolParseError;
+
+static ASCII_LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn interned_letter(b: u8) -> Option<&'static [u8]> {
+    let i: usize = ASCII_LETTERS.iter().position(|c| *c == b)?;
+    ASCII_LETTERS.get(i..i + 1)
+}
 
 pub trait AsciiByteToBool {
     type Error: std::error::Error;
@@ -25,81 +35,160 @@ pub trait AsciiByteToBool {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct AsciiByteToBoolPair {
+pub struct AsciiByteToBoolPair<E = io::Error> {
     pub true_value: u8,
     pub false_value: u8,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> fmt::Debug for AsciiByteToBoolPair<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsciiByteToBoolPair")
+            .field("true_value", &self.true_value)
+            .field("false_value", &self.false_value)
+            .finish()
+    }
 }
 
+impl<E> Clone for AsciiByteToBoolPair<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for AsciiByteToBoolPair<E> {}
+
 impl Default for AsciiByteToBoolPair {
     fn default() -> Self {
+        Self::new_one_zero()
+    }
+}
+
+pub const BUILTIN_BYTE_PAIRS: &[AsciiByteToBoolPair] = &[
+    AsciiByteToBoolPair::new_one_zero(),
+    AsciiByteToBoolPair::new_yn(),
+    AsciiByteToBoolPair::new_tf(),
+    AsciiByteToBoolPair::new_ox(),
+];
+
+impl AsciiByteToBoolPair {
+    pub const fn new_one_zero() -> Self {
         Self {
             true_value: b'1',
             false_value: b'0',
+            error: PhantomData,
         }
     }
-}
 
-impl AsciiByteToBoolPair {
-    pub fn new_yn() -> Self {
+    pub const fn new_yn() -> Self {
         Self {
             true_value: b'y',
             false_value: b'n',
+            error: PhantomData,
         }
     }
 
-    pub fn new_tf() -> Self {
+    pub const fn new_tf() -> Self {
         Self {
             true_value: b't',
             false_value: b'f',
+            error: PhantomData,
         }
     }
 
-    pub fn new_ox() -> Self {
+    pub const fn new_ox() -> Self {
         Self {
             true_value: b'o',
             false_value: b'x',
+            error: PhantomData,
         }
     }
 
-    pub fn new_custom(true_value: u8, false_value: u8) -> Self {
+    /// IEC 60417 power symbols: `I` is on (true) and `O` is off (false).
+    pub const fn new_power_symbol() -> Self {
+        Self::new_custom(b'I', b'O')
+    }
+
+    /// EBCDIC `Y` (`0xE8`) is true and EBCDIC `N` (`0xD5`) is false.
+    pub const fn new_ebcdic_yn() -> Self {
+        Self::new_custom(0xE8, 0xD5)
+    }
+
+    pub const fn new_custom(true_value: u8, false_value: u8) -> Self {
         Self {
             true_value,
             false_value,
+            error: PhantomData,
         }
     }
 }
 
 impl AsciiByteToBoolPair {
-    pub fn new_from_true_value(true_value: u8) -> Self {
+    pub const fn new_from_true_value(true_value: u8) -> Self {
         Self {
             true_value,
             false_value: 0,
+            error: PhantomData,
         }
     }
 
-    pub fn new_o() -> Self {
+    pub const fn new_o() -> Self {
         Self::new_from_true_value(b'o')
     }
 
-    pub fn new_o_capital() -> Self {
+    pub const fn new_o_capital() -> Self {
         Self::new_from_true_value(b'O')
     }
 
-    pub fn new_x() -> Self {
+    pub const fn new_x() -> Self {
         Self::new_from_true_value(b'x')
     }
 
-    pub fn new_x_capital() -> Self {
+    pub const fn new_x_capital() -> Self {
         Self::new_from_true_value(b'X')
     }
 }
 
-impl AsciiByteToBoolPair {
+impl<E> AsciiByteToBoolPair<E> {
+    pub fn with_error<F>(self) -> AsciiByteToBoolPair<F> {
+        AsciiByteToBoolPair {
+            true_value: self.true_value,
+            false_value: self.false_value,
+            error: PhantomData,
+        }
+    }
+
+    pub fn to_static_bytes_pair(&self) -> Option<AsciiBytesToBoolPair<E>> {
+        let true_value: &'static [u8] = interned_letter(self.true_value)?;
+        let false_value: &'static [u8] = interned_letter(self.false_value)?;
+        Some(AsciiBytesToBoolPair::new_custom(true_value, false_value).with_error())
+    }
+
+    pub fn to_byte(&self, value: bool) -> u8 {
+        if value {
+            self.true_value
+        } else {
+            self.false_value
+        }
+    }
+
+    pub fn write_byte(&self, value: bool, out: &mut u8) {
+        *out = self.to_byte(value);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.false_value == 0
+    }
+
+    pub fn values(&self) -> (u8, u8) {
+        (self.true_value, self.false_value)
+    }
+
     pub fn into_lower(self) -> Self {
         Self {
             true_value: self.true_value.to_ascii_lowercase(),
             false_value: self.false_value.to_ascii_lowercase(),
+            error: PhantomData,
         }
     }
 
@@ -107,18 +196,19 @@ impl AsciiByteToBoolPair {
         Self {
             true_value: self.true_value.to_ascii_uppercase(),
             false_value: self.false_value.to_ascii_uppercase(),
+            error: PhantomData,
         }
     }
 }
 
-impl AsciiByteToBool for AsciiByteToBoolPair {
-    type Error = io::Error;
+impl<E> AsciiByteToBool for AsciiByteToBoolPair<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
 
     fn invalid_char2error(invalid_char: char) -> Self::Error {
-        io::Error::new(
-            ErrorKind::InvalidInput,
-            format!("Invalid boolean representation: {}", invalid_char),
-        )
+        E::invalid_char(invalid_char)
     }
 
     fn convert(&self, input: u8) -> Result<bool, Self::Error> {
@@ -127,17 +217,41 @@ impl AsciiByteToBool for AsciiByteToBoolPair {
         } else if input == self.false_value {
             Ok(false)
         } else {
-            Err(io::Error::new(
-                ErrorKind::InvalidInput,
-                "Invalid boolean representation",
-            ))
+            Err(E::invalid(&[input]))
         }
     }
 }
 
+/// Converts the single byte at `index` of a larger record, e.g. a `Y`/`N`
+/// flag at a fixed column.
+///
+/// A record too short to contain `index` is rejected as too short.
+pub struct PositionalConverter<E = io::Error> {
+    pub index: usize,
+    pub inner: AsciiByteToBoolPair<E>,
+}
+
+impl<E> AsciiBytesToBool for PositionalConverter<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        let b: u8 = *input
+            .get(self.index)
+            .ok_or_else(|| E::too_short(self.index.saturating_add(1)))?;
+        AsciiByteToBool::convert(&self.inner, b)
+    }
+}
+
 #[cfg(test)]
 mod ascii_byte_tests {
-    use crate::ascii_byte::{AsciiByteToBool, AsciiByteToBoolPair};
+    use crate::ascii_byte::{
+        AsciiByteToBool, AsciiByteToBoolPair, BUILTIN_BYTE_PAIRS, PositionalConverter,
+    };
+    use crate::ascii_bytes::AsciiBytesToBool;
+    use crate::error::test_support::CustomError;
     use std::io::{Error, ErrorKind};
 
     fn err_msg(err: &Error) -> String {
@@ -181,6 +295,37 @@ mod ascii_byte_tests {
         assert!(!pair.convert(b'x').unwrap());
     }
 
+    #[test]
+    fn write_byte_sets_canonical_byte() {
+        let pair = AsciiByteToBoolPair::new_yn();
+        assert_eq!(pair.to_byte(true), b'y');
+        assert_eq!(pair.to_byte(false), b'n');
+
+        let mut frame: [u8; 3] = *b"F:?";
+        pair.write_byte(true, &mut frame[2]);
+        assert_eq!(&frame, b"F:y");
+        pair.write_byte(false, &mut frame[2]);
+        assert_eq!(&frame, b"F:n");
+    }
+
+    #[test]
+    fn is_open_detects_single_value_pairs() {
+        assert!(AsciiByteToBoolPair::new_o().is_open());
+        assert!(AsciiByteToBoolPair::new_x_capital().is_open());
+        assert!(!AsciiByteToBoolPair::new_yn().is_open());
+        assert!(!AsciiByteToBoolPair::default().is_open());
+    }
+
+    #[test]
+    fn values_returns_true_then_false() {
+        assert_eq!(AsciiByteToBoolPair::new_one_zero().values(), (b'1', b'0'));
+        assert_eq!(AsciiByteToBoolPair::new_yn().values(), (b'y', b'n'));
+        assert_eq!(
+            AsciiByteToBoolPair::new_custom(b'+', b'-').values(),
+            (b'+', b'-')
+        );
+    }
+
     #[test]
     fn into_lower_and_into_upper_work() {
         let pair = AsciiByteToBoolPair::new_tf();
@@ -307,4 +452,109 @@ mod ascii_byte_tests {
         test_pair(pair_x_cap, '\0', false);
         assert!(pair_x_cap.convert_ascii_char('x').is_err());
     }
+
+    #[test]
+    fn custom_error_type_is_used() {
+        let pair = AsciiByteToBoolPair::new_yn().with_error::<CustomError>();
+
+        assert!(pair.convert(b'y').unwrap());
+        assert!(!pair.convert(b'n').unwrap());
+
+        let err = pair.convert(b'z').unwrap_err();
+        assert_eq!(err.0, b"z");
+
+        let err = pair.convert_ascii_char('\u{3042}').unwrap_err();
+        assert_eq!(err.0, "\u{3042}".as_bytes());
+    }
+
+    #[test]
+    fn to_static_bytes_pair_maps_letters() {
+        let pair = AsciiByteToBoolPair::new_yn()
+            .to_static_bytes_pair()
+            .unwrap();
+        assert_eq!(pair.true_value, b"y");
+        assert_eq!(pair.false_value, b"n");
+        assert!(pair.convert(b"y").unwrap());
+        assert!(!pair.convert(b"n").unwrap());
+
+        let pair = AsciiByteToBoolPair::new_custom(b'T', b'F')
+            .to_static_bytes_pair()
+            .unwrap();
+        assert_eq!(pair.true_value, b"T");
+        assert_eq!(pair.false_value, b"F");
+    }
+
+    #[test]
+    fn to_static_bytes_pair_rejects_non_letters() {
+        assert!(
+            AsciiByteToBoolPair::default()
+                .to_static_bytes_pair()
+                .is_none()
+        );
+        assert!(
+            AsciiByteToBoolPair::new_o()
+                .to_static_bytes_pair()
+                .is_none()
+        );
+        assert!(
+            AsciiByteToBoolPair::new_custom(b'y', b'!')
+                .to_static_bytes_pair()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn builtin_byte_pairs_convert_their_values() {
+        assert_eq!(BUILTIN_BYTE_PAIRS.len(), 4);
+        for pair in BUILTIN_BYTE_PAIRS {
+            assert!(pair.convert(pair.true_value).unwrap());
+            assert!(!pair.convert(pair.false_value).unwrap());
+        }
+
+        let default = AsciiByteToBoolPair::default();
+        assert_eq!(BUILTIN_BYTE_PAIRS[0].true_value, default.true_value);
+        assert_eq!(BUILTIN_BYTE_PAIRS[0].false_value, default.false_value);
+    }
+
+    #[test]
+    fn ebcdic_yn_pair_converts_correctly() {
+        let pair = AsciiByteToBoolPair::new_ebcdic_yn();
+
+        assert!(pair.convert(0xE8).unwrap());
+        assert!(!pair.convert(0xD5).unwrap());
+        assert!(pair.convert(b'Y').is_err());
+        assert!(pair.convert(b'N').is_err());
+    }
+
+    #[test]
+    fn power_symbol_pair_converts_correctly() {
+        let pair = AsciiByteToBoolPair::new_power_symbol();
+
+        assert!(pair.convert(b'I').unwrap());
+        assert!(!pair.convert(b'O').unwrap());
+        assert!(pair.convert(b'i').is_err());
+        assert!(pair.convert(b'0').is_err());
+    }
+
+    #[test]
+    fn positional_converter_reads_byte_at_index() {
+        let conv = PositionalConverter {
+            index: 3,
+            inner: AsciiByteToBoolPair::new_custom(b'Y', b'N'),
+        };
+
+        assert!(conv.convert(b"202Y0101").unwrap());
+        assert!(!conv.convert(b"202N0101").unwrap());
+        assert!(conv.convert(b"2021").is_err());
+
+        let err = conv.convert(b"202").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err_msg(&err).contains("min 4 bytes"));
+
+        let conv = PositionalConverter {
+            index: usize::MAX,
+            inner: AsciiByteToBoolPair::new_yn(),
+        };
+        assert!(conv.convert(b"y").is_err());
+    }
 }
@@ -1,16 +1,415 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fmt;
 use std::io;
+use std::marker::PhantomData;
+use std::str::FromStr;
 
 use io::ErrorKind;
 
+use crate::error::{BoolParseError, INVALID_BOOLEAN_REPRESENTATION};
+
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn percent_decode(input: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded: Vec<u8> = Vec::with_capacity(input.len());
+    let mut i = input.iter();
+    while let Some(b) = i.next() {
+        if *b != b'%' {
+            decoded.push(*b);
+            continue;
+        }
+        let hi: u8 = hex_digit(*i.next()?)?;
+        let lo: u8 = hex_digit(*i.next()?)?;
+        decoded.push(hi << 4 | lo);
+    }
+    Some(decoded)
+}
+
+fn hex_unescape(input: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded: Vec<u8> = Vec::with_capacity(input.len());
+    let mut i = input.iter();
+    while let Some(b) = i.next() {
+        if *b != b'\\' {
+            decoded.push(*b);
+            continue;
+        }
+        if *i.next()? != b'x' {
+            return None;
+        }
+        let hi: u8 = hex_digit(*i.next()?)?;
+        let lo: u8 = hex_digit(*i.next()?)?;
+        decoded.push(hi << 4 | lo);
+    }
+    Some(decoded)
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions and
+/// transpositions of adjacent bytes each cost 1.
+///
+/// Keeps three rows of `b.len() + 1` entries, so memory is `O(b.len())`
+/// however long `a` is.
+pub(crate) fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur: Vec<usize> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost: usize = usize::from(a[i - 1] != b[j - 1]);
+            let mut d: usize = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if 1 < i && 1 < j && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(before[j - 2] + 1);
+            }
+            cur[j] = d;
+        }
+        std::mem::swap(&mut before, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+pub fn match_bool(input: &[u8], true_value: &[u8], false_value: &[u8]) -> Option<bool> {
+    if input == true_value {
+        Some(true)
+    } else if input == false_value {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 pub trait AsciiBytesToBool {
     type Error: std::error::Error;
 
     fn convert(&self, input: &[u8]) -> Result<bool, Self::Error>;
+
+    fn convert_flag(&self, input: Option<&[u8]>) -> Result<bool, Self::Error> {
+        match input {
+            None => Ok(true),
+            Some(i) => self.convert(i),
+        }
+    }
+
+    fn convert_map<T, F>(&self, input: &[u8], f: F) -> Result<T, Self::Error>
+    where
+        F: Fn(bool) -> T,
+        Self: Sized,
+    {
+        self.convert(input).map(f)
+    }
+
+    fn convert_flag_typed<Tag>(&self, input: &[u8]) -> Result<Flag<Tag>, Self::Error>
+    where
+        Self: Sized,
+    {
+        self.convert(input).map(Flag::new)
+    }
+
+    fn convert_into(&self, input: &[u8], out: &mut bool) -> Result<(), Self::Error> {
+        *out = self.convert(input)?;
+        Ok(())
+    }
+
+    fn convert_recoverable<'a>(&self, input: &'a [u8]) -> Result<bool, &'a [u8]> {
+        self.convert(input).map_err(|_| input)
+    }
+
+    /// Exact matches score `1.0`; converters with a fuzzy fallback such as
+    /// [`crate::ascii_bytes_wrapper::TypoTolerantConverter`] score near
+    /// matches lower.
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        self.convert(input).map(|b| (b, 1.0))
+    }
+
+    fn is_true(&self, input: &[u8]) -> bool {
+        matches!(self.convert(input), Ok(true))
+    }
+
+    fn is_false(&self, input: &[u8]) -> bool {
+        matches!(self.convert(input), Ok(false))
+    }
+
+    fn convert_with_maybe(
+        &self,
+        input: &[u8],
+        maybe_token: &[u8],
+        maybe_value: bool,
+    ) -> Result<bool, Self::Error> {
+        if input == maybe_token {
+            return Ok(maybe_value);
+        }
+        self.convert(input)
+    }
+
+    fn convert_na(&self, input: &[u8], na_tokens: &[&[u8]]) -> Result<Option<bool>, Self::Error> {
+        if na_tokens.iter().any(|na| input.eq_ignore_ascii_case(na)) {
+            return Ok(None);
+        }
+        self.convert(input).map(Some)
+    }
+
+    fn convert_to_u8(&self, input: &[u8]) -> Result<u8, Self::Error> {
+        self.convert(input).map(u8::from)
+    }
+
+    fn convert_cmp_key(&self, input: &[u8]) -> Result<u8, Self::Error> {
+        self.convert_to_u8(input)
+    }
+
+    fn classify(&self, input: &[u8]) -> u8 {
+        self.convert_to_u8(input).unwrap_or(2)
+    }
+
+    #[cfg(feature = "log")]
+    fn convert_or_default_logged(&self, input: &[u8], default: bool, field: &str) -> bool {
+        self.convert(input).unwrap_or_else(|e| {
+            log::warn!(
+                "field={} input={:?} default={} error={}",
+                field,
+                String::from_utf8_lossy(input),
+                default,
+                e,
+            );
+            default
+        })
+    }
+
+    fn convert_fixed(&self, field: &[u8], pad: u8) -> Result<bool, Self::Error> {
+        let start: usize = field.iter().position(|b| *b != pad).unwrap_or(field.len());
+        let end: usize = field
+            .iter()
+            .rposition(|b| *b != pad)
+            .map_or(start, |i| i + 1);
+        self.convert(&field[start..end])
+    }
+
+    fn convert_trim_matches(&self, input: &[u8], trim: &[u8]) -> Result<bool, Self::Error> {
+        let start: usize = input
+            .iter()
+            .position(|b| !trim.contains(b))
+            .unwrap_or(input.len());
+        let end: usize = input
+            .iter()
+            .rposition(|b| !trim.contains(b))
+            .map_or(start, |i| i + 1);
+        self.convert(&input[start..end])
+    }
+
+    fn convert_first_line(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        let line: &[u8] = input.split(|b| *b == b'\n').next().unwrap_or(input);
+        self.convert(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    fn convert_strip_comment(&self, input: &[u8], comment: u8) -> Result<bool, Self::Error> {
+        let end: usize = input
+            .iter()
+            .position(|b| *b == comment)
+            .unwrap_or(input.len());
+        self.convert(input[..end].trim_ascii())
+    }
+
+    fn convert_cstr(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        let end: usize = input.iter().position(|b| *b == 0).unwrap_or(input.len());
+        self.convert(&input[..end])
+    }
+
+    fn convert_bounded(&self, input: &[u8], max_len: usize) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        if max_len < input.len() {
+            return Err(Self::Error::too_long(max_len));
+        }
+        self.convert(input)
+    }
+
+    fn convert_split<'a>(
+        &'a self,
+        input: &'a [u8],
+        delim: u8,
+    ) -> impl Iterator<Item = Result<bool, Self::Error>> + 'a
+    where
+        Self: Sized,
+    {
+        input.split(move |b| *b == delim).map(|s| self.convert(s))
+    }
+
+    fn convert_nul_delimited<'a>(
+        &'a self,
+        input: &'a [u8],
+    ) -> impl Iterator<Item = Result<bool, Self::Error>> + 'a
+    where
+        Self: Sized,
+    {
+        let records: &[u8] = input.strip_suffix(b"\0").unwrap_or(input);
+        records
+            .split(|b| *b == 0)
+            .filter(move |_| !input.is_empty())
+            .map(|s| self.convert(s))
+    }
+
+    fn classify_fields<'a>(
+        &'a self,
+        input: &'a [u8],
+        field_len: usize,
+    ) -> impl Iterator<Item = Result<bool, Self::Error>> + 'a
+    where
+        Self: Sized,
+        Self::Error: BoolParseError,
+    {
+        let aligned: bool = field_len != 0 && input.len().is_multiple_of(field_len);
+        let misaligned = (!aligned).then(|| Err(Self::Error::malformed(input)));
+        misaligned.into_iter().chain(
+            input
+                .chunks(field_len.max(1))
+                .filter(move |_| aligned)
+                .map(|field| self.convert(field)),
+        )
+    }
+
+    fn convert_percent_decoded(&self, input: &[u8]) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        let decoded: Vec<u8> =
+            percent_decode(input).ok_or_else(|| Self::Error::malformed(input))?;
+        self.convert(&decoded)
+    }
+
+    /// Treats anything outside `false_values` and `invalid_values` as true,
+    /// except the converter's own false tokens. `invalid_values` take
+    /// precedence over `false_values`.
+    fn convert_truthy_strict(
+        &self,
+        input: &[u8],
+        false_values: &[&[u8]],
+        invalid_values: &[&[u8]],
+    ) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        if invalid_values.contains(&input) {
+            return Err(Self::Error::invalid(input));
+        }
+        if false_values.contains(&input) {
+            return Ok(false);
+        }
+        Ok(self.convert(input).unwrap_or(true))
+    }
+
+    fn convert_hex_unescape(&self, input: &[u8]) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        let decoded: Vec<u8> = hex_unescape(input).ok_or_else(|| Self::Error::malformed(input))?;
+        self.convert(&decoded)
+    }
+
+    #[cfg(feature = "base64")]
+    fn convert_base64(&self, input: &[u8]) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        use base64::Engine;
+
+        let decoded: Vec<u8> = base64::engine::general_purpose::STANDARD
+            .decode(input)
+            .map_err(|_| Self::Error::malformed(input))?;
+        self.convert(&decoded)
+    }
+
+    fn convert_os(&self, input: &OsStr) -> Result<bool, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        match input.to_str() {
+            Some(s) => self.convert(s.as_bytes()),
+            None => Err(Self::Error::invalid(input.as_encoded_bytes())),
+        }
+    }
+
+    fn convert_bracket_list(&self, input: &[u8]) -> Result<Vec<bool>, Self::Error>
+    where
+        Self::Error: BoolParseError,
+    {
+        let inner: &[u8] = input
+            .trim_ascii()
+            .strip_prefix(b"[")
+            .and_then(|i| i.strip_suffix(b"]"))
+            .ok_or_else(|| Self::Error::malformed(input))?;
+        if inner.trim_ascii().is_empty() {
+            return Ok(vec![]);
+        }
+        inner
+            .split(|b| *b == b',')
+            .map(|s| self.convert(s.trim_ascii()))
+            .collect()
+    }
+
+    fn convert_many_or<'a, I>(&self, inputs: I, default: bool) -> Vec<bool>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+        Self: Sized,
+    {
+        inputs
+            .into_iter()
+            .map(|input| self.convert(input).unwrap_or(default))
+            .collect()
+    }
+
+    fn validate_all<'a, I>(&self, inputs: I) -> Result<Vec<bool>, Vec<(usize, Vec<u8>)>>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+        Self: Sized,
+    {
+        let mut parsed: Vec<bool> = vec![];
+        let mut invalid: Vec<(usize, Vec<u8>)> = vec![];
+        for (i, input) in inputs.into_iter().enumerate() {
+            match self.convert(input) {
+                Ok(b) => parsed.push(b),
+                Err(_) => invalid.push((i, input.to_vec())),
+            }
+        }
+        if invalid.is_empty() {
+            Ok(parsed)
+        } else {
+            Err(invalid)
+        }
+    }
+
+    fn summarize<'a, I>(&self, inputs: I) -> String
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+        Self: Sized,
+    {
+        let mut counts: [usize; 3] = [0; 3];
+        for input in inputs {
+            counts[usize::from(self.classify(input))] += 1;
+        }
+        let [f, t, invalid] = counts;
+        format!(
+            "parsed {} tokens: {} true, {} false, {} invalid",
+            t + f + invalid,
+            t,
+            f,
+            invalid,
+        )
+    }
 }
 
-pub struct AsciiBytesToBoolPair {
+pub struct AsciiBytesToBoolPair<E = io::Error> {
     pub true_value: &'static [u8],
     pub false_value: &'static [u8],
+    error: PhantomData<fn() -> E>,
 }
 
 impl Default for AsciiBytesToBoolPair {
@@ -18,15 +417,24 @@ impl Default for AsciiBytesToBoolPair {
         Self {
             true_value: b"true",
             false_value: b"false",
+            error: PhantomData,
         }
     }
 }
 
 impl AsciiBytesToBoolPair {
+    /// The length of `disabled`, the longest token of the built-in constructors.
+    pub const LONGEST_BUILTIN: usize = 8;
+
+    pub fn static_error_message() -> &'static str {
+        INVALID_BOOLEAN_REPRESENTATION
+    }
+
     pub fn new_yes_no() -> Self {
         Self {
             true_value: b"yes",
             false_value: b"no",
+            error: PhantomData,
         }
     }
 
@@ -34,6 +442,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"y",
             false_value: b"n",
+            error: PhantomData,
         }
     }
 
@@ -41,6 +450,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"o",
             false_value: b"x",
+            error: PhantomData,
         }
     }
 
@@ -48,6 +458,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"t",
             false_value: b"f",
+            error: PhantomData,
         }
     }
 
@@ -55,6 +466,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"on",
             false_value: b"off",
+            error: PhantomData,
         }
     }
 
@@ -62,6 +474,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"Yes",
             false_value: b"No",
+            error: PhantomData,
         }
     }
 
@@ -69,6 +482,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"On",
             false_value: b"Off",
+            error: PhantomData,
         }
     }
 
@@ -76,6 +490,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"True",
             false_value: b"False",
+            error: PhantomData,
         }
     }
 
@@ -83,13 +498,43 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: b"true",
             false_value: b"false",
+            error: PhantomData,
+        }
+    }
+
+    pub fn new_enabled_disabled() -> Self {
+        Self {
+            true_value: b"enabled",
+            false_value: b"disabled",
+            error: PhantomData,
         }
     }
 
+    pub fn new_enabled_disabled_capitalised() -> Self {
+        Self {
+            true_value: b"Enabled",
+            false_value: b"Disabled",
+            error: PhantomData,
+        }
+    }
+
+    pub fn new_accept_reject() -> Self {
+        Self::new_custom(b"accept", b"reject")
+    }
+
+    pub fn new_accept_reject_capitalised() -> Self {
+        Self::new_custom(b"Accept", b"Reject")
+    }
+
+    pub fn new_positive_negative() -> Self {
+        Self::new_custom(b"positive", b"negative")
+    }
+
     pub fn new_custom(true_value: &'static [u8], false_value: &'static [u8]) -> Self {
         Self {
             true_value,
             false_value,
+            error: PhantomData,
         }
     }
 }
@@ -99,6 +544,7 @@ impl AsciiBytesToBoolPair {
         Self {
             true_value: tv,
             false_value: b"",
+            error: PhantomData,
         }
     }
 
@@ -119,164 +565,733 @@ impl AsciiBytesToBoolPair {
     }
 }
 
-impl AsciiBytesToBool for AsciiBytesToBoolPair {
-    type Error = io::Error;
+impl<E> AsciiBytesToBoolPair<E> {
+    pub fn with_error<F>(self) -> AsciiBytesToBoolPair<F> {
+        AsciiBytesToBoolPair {
+            true_value: self.true_value,
+            false_value: self.false_value,
+            error: PhantomData,
+        }
+    }
 
-    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
-        if input == self.true_value {
-            Ok(true)
-        } else if input == self.false_value {
-            Ok(false)
+    pub fn to_bytes(&self, value: bool) -> &'static [u8] {
+        if value {
+            self.true_value
         } else {
-            Err(io::Error::new(
-                ErrorKind::InvalidInput,
-                "Invalid boolean representation",
-            ))
+            self.false_value
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::ErrorKind;
+    pub fn to_bit_char(&self, value: bool) -> char {
+        if value { '1' } else { '0' }
+    }
 
-    fn err_msg(err: &std::io::Error) -> String {
-        err.to_string()
+    pub fn is_canonical(&self, input: &[u8]) -> bool {
+        input == self.true_value || input == self.false_value
     }
 
-    #[test]
-    fn default_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::default();
+    pub fn opposite_token(&self, value: bool) -> &[u8] {
+        self.to_bytes(!value)
+    }
 
-        assert!(pair.convert(b"true").unwrap());
-        assert!(!pair.convert(b"false").unwrap());
-        let err = pair.convert(b"").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    pub fn to_string_lossy(&self, value: bool) -> String {
+        String::from_utf8_lossy(self.to_bytes(value)).into_owned()
     }
 
-    #[test]
-    fn yes_no_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_yes_no();
-        assert!(pair.convert(b"yes").unwrap());
-        assert!(!pair.convert(b"no").unwrap());
+    pub fn accepted_strings(&self) -> Vec<String> {
+        [self.true_value, self.false_value]
+            .iter()
+            .map(|t| String::from_utf8_lossy(t).into_owned())
+            .collect()
     }
 
-    #[test]
-    fn y_n_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_y_n();
-        assert!(pair.convert(b"y").unwrap());
-        assert!(!pair.convert(b"n").unwrap());
+    /// Formats a `true_value:false_value` spec for
+    /// [`crate::ascii_bytes_owned::AsciiBytesToBoolOwned::parse_spec`].
+    ///
+    /// Returns `None` when the spec would not round-trip: a token is not UTF-8,
+    /// or the true value contains `:` (a `:` in the false value is fine, as
+    /// parsing splits at the first one).
+    pub fn to_spec(&self) -> Option<String> {
+        let t: &str = std::str::from_utf8(self.true_value).ok()?;
+        let f: &str = std::str::from_utf8(self.false_value).ok()?;
+        (!t.contains(':')).then(|| format!("{}:{}", t, f))
     }
 
-    #[test]
-    fn o_x_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_o_x();
-        assert!(pair.convert(b"o").unwrap());
-        assert!(!pair.convert(b"x").unwrap());
+    pub fn to_shell_arg(&self, value: bool) -> String {
+        let token: &[u8] = self.to_bytes(value);
+        let s: String = String::from_utf8_lossy(token).into_owned();
+        if !token.is_empty() && token.iter().all(u8::is_ascii_alphanumeric) {
+            return s;
+        }
+        format!("'{}'", s.replace('\'', "'\\''"))
     }
 
-    #[test]
-    fn t_f_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_t_f();
-        assert!(pair.convert(b"t").unwrap());
-        assert!(!pair.convert(b"f").unwrap());
+    pub fn is_open(&self) -> bool {
+        self.false_value.is_empty()
     }
 
-    #[test]
-    fn on_off_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_on_off();
-        assert!(pair.convert(b"on").unwrap());
-        assert!(!pair.convert(b"off").unwrap());
+    pub fn values(&self) -> (&'static [u8], &'static [u8]) {
+        (self.true_value, self.false_value)
     }
 
-    #[test]
-    fn capitalised_pairs_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_yes_no_capitalised();
-        assert!(pair.convert(b"Yes").unwrap());
-        assert!(!pair.convert(b"No").unwrap());
+    pub fn as_entries(&self) -> Vec<(&[u8], bool)> {
+        vec![(self.true_value, true), (self.false_value, false)]
+    }
 
-        let pair = AsciiBytesToBoolPair::new_on_off_capitalised();
-        assert!(pair.convert(b"On").unwrap());
-        assert!(!pair.convert(b"Off").unwrap());
+    pub fn vocabulary_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
 
-        let pair = AsciiBytesToBoolPair::new_true_false_capitalised();
-        assert!(pair.convert(b"True").unwrap());
-        assert!(!pair.convert(b"False").unwrap());
+        let mut entries = self.as_entries();
+        entries.sort();
+        entries
+            .iter()
+            .flat_map(|(token, value)| {
+                let len = (token.len() as u64).to_le_bytes();
+                len.into_iter()
+                    .chain(token.iter().copied())
+                    .chain([u8::from(*value)])
+            })
+            .fold(FNV_OFFSET, |h, b| {
+                (h ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+            })
     }
 
-    #[test]
-    fn custom_pair_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_custom(b"ok", b"nope");
-        assert!(pair.convert(b"ok").unwrap());
-        assert!(!pair.convert(b"nope").unwrap());
+    /// Fails with a borrowed static message for unknown ASCII input and an
+    /// owned message listing the bytes for non-ASCII input.
+    pub fn convert_cow(&self, input: &[u8]) -> Result<bool, Cow<'static, str>> {
+        if input == self.true_value {
+            Ok(true)
+        } else if input == self.false_value {
+            Ok(false)
+        } else if input.is_ascii() {
+            Err(Cow::Borrowed(INVALID_BOOLEAN_REPRESENTATION))
+        } else {
+            Err(Cow::Owned(format!(
+                "Non-ASCII boolean representation: {:x?}",
+                input
+            )))
+        }
     }
 
-    #[test]
-    fn invalid_input_returns_error() {
-        let pair = AsciiBytesToBoolPair::default();
+    pub fn same_vocabulary<F>(&self, other: &AsciiBytesToBoolPair<F>) -> bool {
+        self.true_value == other.true_value && self.false_value == other.false_value
+    }
 
-        let err = pair.convert(b"maybe").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
-        assert!(err_msg(&err).contains("Invalid boolean representation"));
+    /// A heuristic for dirty data: never fails.
+    ///
+    /// Unparsable input maps to whichever token is nearer by edit distance,
+    /// falling back to `false` on ties. Input more than twice as long as the
+    /// longer token is `false` without computing any distance.
+    pub fn convert_best_effort(&self, input: &[u8]) -> bool {
+        if let Some(b) = match_bool(input, self.true_value, self.false_value) {
+            return b;
+        }
+        let longest: usize = self.true_value.len().max(self.false_value.len());
+        if longest.saturating_mul(2) < input.len() {
+            return false;
+        }
+        let to_true: usize = edit_distance(input, self.true_value);
+        let to_false: usize = edit_distance(input, self.false_value);
+        to_true < to_false
+    }
+}
 
-        let err = pair.convert(b"truest").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+impl<E> AsciiBytesToBoolPair<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    pub fn convert_canonical<'a>(&'a self, input: &[u8]) -> Result<(bool, &'a [u8]), E> {
+        if input == self.true_value {
+            Ok((true, self.true_value))
+        } else if input == self.false_value {
+            Ok((false, self.false_value))
+        } else {
+            Err(E::invalid(input))
+        }
     }
 
-    #[test]
-    fn different_length_inputs_are_rejected() {
-        let pair = AsciiBytesToBoolPair::new_t_f();
-        assert!(pair.convert(b"t").is_ok());
-        assert!(pair.convert(b"tt").is_err());
+    pub fn convert_canonical_ignore_case<'a>(
+        &'a self,
+        input: &[u8],
+    ) -> Result<(bool, &'a [u8]), E> {
+        if input.eq_ignore_ascii_case(self.true_value) {
+            Ok((true, self.true_value))
+        } else if input.eq_ignore_ascii_case(self.false_value) {
+            Ok((false, self.false_value))
+        } else {
+            Err(E::invalid(input))
+        }
     }
 
-    #[test]
-    fn null_slice_returns_error() {
-        let pair = AsciiBytesToBoolPair::default();
-        let err = pair.convert(&[]).unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    /// An empty token, such as the false value of an open pair, never matches:
+    /// it would consume nothing.
+    pub fn convert_consume<'a>(&self, input: &'a [u8]) -> Result<(bool, &'a [u8]), E> {
+        let mut candidates = [(self.true_value, true), (self.false_value, false)];
+        candidates.sort_by_key(|(token, _)| std::cmp::Reverse(token.len()));
+        candidates
+            .iter()
+            .filter(|(token, _)| !token.is_empty())
+            .find_map(|(token, value)| input.strip_prefix(*token).map(|rest| (*value, rest)))
+            .ok_or_else(|| E::invalid(input))
     }
 
-    #[test]
-    fn new_from_true_value_converts_correctly() {
-        let pair = AsciiBytesToBoolPair::new_from_true_value(b"o");
+    pub fn convert_diff(&self, input: &[u8]) -> Result<bool, (E, usize)> {
+        self.convert(input).map_err(|e| {
+            let at: usize = [self.true_value, self.false_value]
+                .iter()
+                .filter(|token| token.len() == input.len())
+                .filter_map(|token| input.iter().zip(token.iter()).position(|(x, y)| x != y))
+                .max()
+                .unwrap_or(input.len());
+            (e, at)
+        })
+    }
 
-        assert!(pair.convert(b"o").unwrap());
+    pub fn convert_with_cost(&self, input: &[u8]) -> (Result<bool, E>, usize) {
+        let compared = |token: &[u8]| -> usize {
+            if token.len() != input.len() {
+                return 0;
+            }
+            input
+                .iter()
+                .zip(token)
+                .position(|(x, y)| x != y)
+                .map_or(token.len(), |i| i + 1)
+        };
+        let result: Result<bool, E> = self.convert(input);
+        let cost: usize = match result {
+            Ok(true) => compared(self.true_value),
+            _ => compared(self.true_value) + compared(self.false_value),
+        };
+        (result, cost)
+    }
 
-        assert!(!pair.convert(b"").unwrap());
+    pub fn convert_normalized(&self, input: &[u8]) -> Result<bool, E> {
+        let trimmed: &[u8] = input.trim_ascii();
+        if trimmed.eq_ignore_ascii_case(self.true_value) {
+            Ok(true)
+        } else if trimmed.eq_ignore_ascii_case(self.false_value) {
+            Ok(false)
+        } else {
+            Err(E::invalid(input))
+        }
+    }
 
-        let err = pair.convert(b"x").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    /// Like [`Self::convert_normalized`], additionally passing the stored token
+    /// for the result to `emit`; nothing is emitted on failure.
+    pub fn convert_canonicalize<F: FnMut(&[u8])>(
+        &self,
+        input: &[u8],
+        mut emit: F,
+    ) -> Result<bool, E> {
+        let b: bool = self.convert_normalized(input)?;
+        emit(self.to_bytes(b));
+        Ok(b)
     }
 
-    #[test]
-    fn new_o_is_equivalent_to_new_from_true_value() {
-        let pair_from = AsciiBytesToBoolPair::new_from_true_value(b"o");
-        let pair_o = AsciiBytesToBoolPair::new_o();
+    pub fn convert_progressive(&self, input: &[u8]) -> Result<bool, E> {
+        self.convert_canonical(input)
+            .or_else(|_| self.convert_canonical(input.trim_ascii()))
+            .map(|(b, _)| b)
+            .or_else(|_| self.convert_normalized(input))
+    }
 
-        assert_eq!(pair_from.true_value, pair_o.true_value);
-        assert_eq!(pair_from.false_value, pair_o.false_value);
+    /// Like [`Self::convert_canonical`], but the matched token is guaranteed non-empty.
+    ///
+    /// Input matching an empty token (the false value of
+    /// [`AsciiBytesToBoolPair::new_from_true_value`]) is rejected as invalid
+    /// rather than mapped to a sentinel token.
+    pub fn convert_canonical_token(&self, input: &[u8]) -> Result<(bool, CanonicalToken), E> {
+        let (b, _) = self.convert_canonical(input)?;
+        let token: CanonicalToken =
+            CanonicalToken::new(self.to_bytes(b)).ok_or_else(|| E::invalid(input))?;
+        Ok((b, token))
+    }
 
-        assert!(pair_o.convert(b"o").unwrap());
-        assert!(!pair_o.convert(b"").unwrap());
+    pub fn convert_with_flags(&self, input: &[u8]) -> Result<(bool, ConvertFlags), E> {
+        let trimmed: &[u8] = input.trim_ascii();
+        let flags = ConvertFlags {
+            trimmed: trimmed.len() != input.len(),
+            case_folded: false,
+        };
+        if let Ok((b, _)) = self.convert_canonical(trimmed) {
+            return Ok((b, flags));
+        }
+        let (b, _) = self.convert_canonical_ignore_case(trimmed)?;
+        Ok((
+            b,
+            ConvertFlags {
+                case_folded: true,
+                ..flags
+            },
+        ))
     }
+}
 
-    #[test]
-    fn new_o_capital() {
-        let pair = AsciiBytesToBoolPair::new_o_capital();
+pub struct Flag<Tag>(bool, PhantomData<fn() -> Tag>);
 
-        assert!(pair.convert(b"O").unwrap());
+impl<Tag> Flag<Tag> {
+    pub fn new(value: bool) -> Self {
+        Self(value, PhantomData)
+    }
 
-        let err = pair.convert(b"o").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    pub fn get(&self) -> bool {
+        self.0
+    }
+}
 
-        assert!(!pair.convert(b"").unwrap());
+impl<Tag> fmt::Debug for Flag<Tag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Flag").field(&self.0).finish()
     }
+}
 
-    #[test]
-    fn new_x() {
-        let pair = AsciiBytesToBoolPair::new_x();
+impl<Tag> Clone for Flag<Tag> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tag> Copy for Flag<Tag> {}
+
+impl<Tag> PartialEq for Flag<Tag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Tag> Eq for Flag<Tag> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalToken(&'static [u8]);
+
+impl CanonicalToken {
+    pub fn new(token: &'static [u8]) -> Option<Self> {
+        (!token.is_empty()).then_some(Self(token))
+    }
+
+    pub fn as_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConvertFlags {
+    pub trimmed: bool,
+    pub case_folded: bool,
+}
+
+impl<E> AsciiBytesToBool for AsciiBytesToBoolPair<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        match_bool(input, self.true_value, self.false_value).ok_or_else(|| E::invalid(input))
+    }
+}
+
+pub struct PrefixMatchConverter<E = io::Error> {
+    pub true_prefix: &'static [u8],
+    pub false_prefix: &'static [u8],
+    error: PhantomData<fn() -> E>,
+}
+
+impl PrefixMatchConverter {
+    pub fn new(true_prefix: &'static [u8], false_prefix: &'static [u8]) -> Self {
+        Self {
+            true_prefix,
+            false_prefix,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> PrefixMatchConverter<E> {
+    pub fn with_error<F>(self) -> PrefixMatchConverter<F> {
+        PrefixMatchConverter {
+            true_prefix: self.true_prefix,
+            false_prefix: self.false_prefix,
+            error: PhantomData,
+        }
+    }
+
+    fn has_prefix(input: &[u8], prefix: &[u8]) -> bool {
+        input.len() >= prefix.len() && input[..prefix.len()].eq_ignore_ascii_case(prefix)
+    }
+}
+
+impl<E> AsciiBytesToBool for PrefixMatchConverter<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        let t: bool = Self::has_prefix(input, self.true_prefix);
+        let f: bool = Self::has_prefix(input, self.false_prefix);
+        match (t, f) {
+            (true, false) => Ok(true),
+            (false, true) => Ok(false),
+            (true, true) => Err(E::ambiguous(input)),
+            (false, false) => Err(E::invalid(input)),
+        }
+    }
+}
+
+/// Classifies input purely by its length, ignoring its content.
+///
+/// This only suits encodings such as `yy` for yes and `y` for no, where the
+/// length alone carries the value. If both lengths are equal, matching input
+/// is rejected as ambiguous.
+pub struct LengthConverter<E = io::Error> {
+    pub true_len: usize,
+    pub false_len: usize,
+    error: PhantomData<fn() -> E>,
+}
+
+impl LengthConverter {
+    pub fn new(true_len: usize, false_len: usize) -> Self {
+        Self {
+            true_len,
+            false_len,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> LengthConverter<E> {
+    pub fn with_error<F>(self) -> LengthConverter<F> {
+        LengthConverter {
+            true_len: self.true_len,
+            false_len: self.false_len,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> AsciiBytesToBool for LengthConverter<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        match (input.len() == self.true_len, input.len() == self.false_len) {
+            (true, false) => Ok(true),
+            (false, true) => Ok(false),
+            (true, true) => Err(E::ambiguous(input)),
+            (false, false) => Err(E::invalid(input)),
+        }
+    }
+}
+
+/// Compares tokens after ASCII-lowercasing and treating `_`, `-` and space as
+/// the same separator, so `always_on`, `ALWAYS-ON` and `Always On` are equal.
+///
+/// No other normalization (trimming, collapsing separators) is applied.
+pub struct CaseStyleTolerantConverter<E = io::Error> {
+    pub true_value: &'static [u8],
+    pub false_value: &'static [u8],
+    error: PhantomData<fn() -> E>,
+}
+
+impl CaseStyleTolerantConverter {
+    pub fn new(true_value: &'static [u8], false_value: &'static [u8]) -> Self {
+        Self {
+            true_value,
+            false_value,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> CaseStyleTolerantConverter<E> {
+    pub fn with_error<F>(self) -> CaseStyleTolerantConverter<F> {
+        CaseStyleTolerantConverter {
+            true_value: self.true_value,
+            false_value: self.false_value,
+            error: PhantomData,
+        }
+    }
+
+    fn normalize(b: u8) -> u8 {
+        match b {
+            b'-' | b' ' => b'_',
+            _ => b.to_ascii_lowercase(),
+        }
+    }
+
+    fn eq_normalized(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b)
+                .all(|(x, y)| Self::normalize(*x) == Self::normalize(*y))
+    }
+}
+
+impl<E> AsciiBytesToBool for CaseStyleTolerantConverter<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        if Self::eq_normalized(input, self.true_value) {
+            Ok(true)
+        } else if Self::eq_normalized(input, self.false_value) {
+            Ok(false)
+        } else {
+            Err(E::invalid(input))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinBoolStyle {
+    TrueFalse,
+    YesNo,
+    OnOff,
+    YN,
+    TF,
+    OX,
+}
+
+impl BuiltinBoolStyle {
+    pub fn to_pair(&self) -> AsciiBytesToBoolPair {
+        match self {
+            Self::TrueFalse => AsciiBytesToBoolPair::new_true_false(),
+            Self::YesNo => AsciiBytesToBoolPair::new_yes_no(),
+            Self::OnOff => AsciiBytesToBoolPair::new_on_off(),
+            Self::YN => AsciiBytesToBoolPair::new_y_n(),
+            Self::TF => AsciiBytesToBoolPair::new_t_f(),
+            Self::OX => AsciiBytesToBoolPair::new_o_x(),
+        }
+    }
+}
+
+impl FromStr for BuiltinBoolStyle {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "true_false" => Ok(Self::TrueFalse),
+            "yes_no" => Ok(Self::YesNo),
+            "on_off" => Ok(Self::OnOff),
+            "y_n" => Ok(Self::YN),
+            "t_f" => Ok(Self::TF),
+            "o_x" => Ok(Self::OX),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unknown bool style: {}", s),
+            )),
+        }
+    }
+}
+
+pub fn parse_flag_set(input: &[u8]) -> HashSet<Vec<u8>> {
+    input
+        .split(|b| *b == b',')
+        .map(|s| s.trim_ascii())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_vec())
+        .collect()
+}
+
+/// Whether `key` appears in a `&`-separated query such as `?verbose&debug`.
+///
+/// A leading `?` is ignored and `key=value` counts as present regardless of
+/// the value.
+pub fn key_present(query: &[u8], key: &[u8]) -> bool {
+    let query: &[u8] = query.strip_prefix(b"?").unwrap_or(query);
+    !key.is_empty()
+        && query
+            .split(|b| *b == b'&')
+            .map(|s| s.split(|b| *b == b'=').next().unwrap_or(s))
+            .any(|k| k == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Str2BoolError;
+    use crate::error::test_support::CustomError;
+    use std::io::ErrorKind;
+
+    fn err_msg(err: &std::io::Error) -> String {
+        err.to_string()
+    }
+
+    #[test]
+    fn default_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert(b"true").unwrap());
+        assert!(!pair.convert(b"false").unwrap());
+        let err = pair.convert(b"").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn yes_no_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+        assert!(pair.convert(b"yes").unwrap());
+        assert!(!pair.convert(b"no").unwrap());
+    }
+
+    #[test]
+    fn y_n_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_y_n();
+        assert!(pair.convert(b"y").unwrap());
+        assert!(!pair.convert(b"n").unwrap());
+    }
+
+    #[test]
+    fn o_x_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_o_x();
+        assert!(pair.convert(b"o").unwrap());
+        assert!(!pair.convert(b"x").unwrap());
+    }
+
+    #[test]
+    fn t_f_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_t_f();
+        assert!(pair.convert(b"t").unwrap());
+        assert!(!pair.convert(b"f").unwrap());
+    }
+
+    #[test]
+    fn on_off_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        assert!(pair.convert(b"on").unwrap());
+        assert!(!pair.convert(b"off").unwrap());
+    }
+
+    #[test]
+    fn capitalised_pairs_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_yes_no_capitalised();
+        assert!(pair.convert(b"Yes").unwrap());
+        assert!(!pair.convert(b"No").unwrap());
+
+        let pair = AsciiBytesToBoolPair::new_on_off_capitalised();
+        assert!(pair.convert(b"On").unwrap());
+        assert!(!pair.convert(b"Off").unwrap());
+
+        let pair = AsciiBytesToBoolPair::new_true_false_capitalised();
+        assert!(pair.convert(b"True").unwrap());
+        assert!(!pair.convert(b"False").unwrap());
+    }
+
+    #[test]
+    fn enabled_disabled_pairs_convert_correctly() {
+        let pair = AsciiBytesToBoolPair::new_enabled_disabled();
+        assert!(pair.convert(b"enabled").unwrap());
+        assert!(!pair.convert(b"disabled").unwrap());
+        assert!(pair.convert(b"enable").is_err());
+
+        let pair = AsciiBytesToBoolPair::new_enabled_disabled_capitalised();
+        assert!(pair.convert(b"Enabled").unwrap());
+        assert!(!pair.convert(b"Disabled").unwrap());
+        assert!(pair.convert(b"Enable").is_err());
+    }
+
+    #[test]
+    fn accept_reject_pairs_convert_correctly() {
+        let pair = AsciiBytesToBoolPair::new_accept_reject();
+        assert!(pair.convert(b"accept").unwrap());
+        assert!(!pair.convert(b"reject").unwrap());
+        assert!(pair.convert(b"acc").is_err());
+        assert!(pair.convert(b"rej").is_err());
+
+        let pair = AsciiBytesToBoolPair::new_accept_reject_capitalised();
+        assert!(pair.convert(b"Accept").unwrap());
+        assert!(!pair.convert(b"Reject").unwrap());
+        assert!(pair.convert(b"accept").is_err());
+    }
+
+    #[test]
+    fn positive_negative_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_positive_negative();
+        assert!(pair.convert(b"positive").unwrap());
+        assert!(!pair.convert(b"negative").unwrap());
+        assert!(pair.convert(b"pos").is_err());
+        assert!(pair.convert(b"neg").is_err());
+    }
+
+    #[test]
+    fn custom_pair_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_custom(b"ok", b"nope");
+        assert!(pair.convert(b"ok").unwrap());
+        assert!(!pair.convert(b"nope").unwrap());
+    }
+
+    #[test]
+    fn invalid_input_returns_error() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        let err = pair.convert(b"maybe").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err_msg(&err).contains("Invalid boolean representation"));
+
+        let err = pair.convert(b"truest").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn different_length_inputs_are_rejected() {
+        let pair = AsciiBytesToBoolPair::new_t_f();
+        assert!(pair.convert(b"t").is_ok());
+        assert!(pair.convert(b"tt").is_err());
+    }
+
+    #[test]
+    fn null_slice_returns_error() {
+        let pair = AsciiBytesToBoolPair::default();
+        let err = pair.convert(&[]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn new_from_true_value_converts_correctly() {
+        let pair = AsciiBytesToBoolPair::new_from_true_value(b"o");
+
+        assert!(pair.convert(b"o").unwrap());
+
+        assert!(!pair.convert(b"").unwrap());
+
+        let err = pair.convert(b"x").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn new_o_is_equivalent_to_new_from_true_value() {
+        let pair_from = AsciiBytesToBoolPair::new_from_true_value(b"o");
+        let pair_o = AsciiBytesToBoolPair::new_o();
+
+        assert_eq!(pair_from.true_value, pair_o.true_value);
+        assert_eq!(pair_from.false_value, pair_o.false_value);
+
+        assert!(pair_o.convert(b"o").unwrap());
+        assert!(!pair_o.convert(b"").unwrap());
+    }
+
+    #[test]
+    fn new_o_capital() {
+        let pair = AsciiBytesToBoolPair::new_o_capital();
+
+        assert!(pair.convert(b"O").unwrap());
+
+        let err = pair.convert(b"o").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        assert!(!pair.convert(b"").unwrap());
+    }
+
+    #[test]
+    fn new_x() {
+        let pair = AsciiBytesToBoolPair::new_x();
 
         assert!(pair.convert(b"x").unwrap());
         assert!(!pair.convert(b"").unwrap());
@@ -295,4 +1310,1125 @@ mod tests {
         let err = pair.convert(b"x").unwrap_err();
         assert_eq!(err.kind(), ErrorKind::InvalidInput);
     }
+
+    #[test]
+    fn convert_flag_treats_absent_as_true() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_flag(None).unwrap());
+        assert!(pair.convert_flag(Some(b"true")).unwrap());
+        assert!(!pair.convert_flag(Some(b"false")).unwrap());
+
+        let err = pair.convert_flag(Some(b"nonsense")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn convert_into_writes_only_on_success() {
+        let pair = AsciiBytesToBoolPair::default();
+        let mut out = false;
+
+        pair.convert_into(b"true", &mut out).unwrap();
+        assert!(out);
+
+        let err = pair.convert_into(b"nonsense", &mut out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(out);
+
+        pair.convert_into(b"false", &mut out).unwrap();
+        assert!(!out);
+    }
+
+    #[test]
+    fn is_true_and_is_false_reject_invalid() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.is_true(b"true"));
+        assert!(!pair.is_false(b"true"));
+
+        assert!(!pair.is_true(b"false"));
+        assert!(pair.is_false(b"false"));
+
+        assert!(!pair.is_true(b"nonsense"));
+        assert!(!pair.is_false(b"nonsense"));
+    }
+
+    #[test]
+    fn custom_error_type_is_used() {
+        let pair = AsciiBytesToBoolPair::new_yes_no().with_error::<CustomError>();
+
+        assert!(pair.convert(b"yes").unwrap());
+        assert!(!pair.convert(b"no").unwrap());
+
+        let err = pair.convert(b"maybe").unwrap_err();
+        assert_eq!(err.0, b"maybe");
+    }
+
+    #[test]
+    fn convert_canonical_returns_stored_token() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert_eq!(
+            pair.convert_canonical(b"true").unwrap(),
+            (true, &b"true"[..])
+        );
+        assert_eq!(
+            pair.convert_canonical(b"false").unwrap(),
+            (false, &b"false"[..])
+        );
+
+        let err = pair.convert_canonical(b"TRUE").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn convert_canonical_ignore_case_normalizes_spelling() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert_eq!(
+            pair.convert_canonical_ignore_case(b"TRUE").unwrap(),
+            (true, &b"true"[..])
+        );
+        assert_eq!(
+            pair.convert_canonical_ignore_case(b"False").unwrap(),
+            (false, &b"false"[..])
+        );
+        assert!(pair.convert_canonical_ignore_case(b"yes").is_err());
+    }
+
+    fn builtin_pairs() -> Vec<AsciiBytesToBoolPair> {
+        vec![
+            AsciiBytesToBoolPair::default(),
+            AsciiBytesToBoolPair::new_yes_no(),
+            AsciiBytesToBoolPair::new_y_n(),
+            AsciiBytesToBoolPair::new_o_x(),
+            AsciiBytesToBoolPair::new_t_f(),
+            AsciiBytesToBoolPair::new_on_off(),
+            AsciiBytesToBoolPair::new_yes_no_capitalised(),
+            AsciiBytesToBoolPair::new_on_off_capitalised(),
+            AsciiBytesToBoolPair::new_true_false_capitalised(),
+            AsciiBytesToBoolPair::new_true_false(),
+            AsciiBytesToBoolPair::new_enabled_disabled(),
+            AsciiBytesToBoolPair::new_enabled_disabled_capitalised(),
+            AsciiBytesToBoolPair::new_accept_reject(),
+            AsciiBytesToBoolPair::new_accept_reject_capitalised(),
+            AsciiBytesToBoolPair::new_positive_negative(),
+            AsciiBytesToBoolPair::new_o(),
+            AsciiBytesToBoolPair::new_o_capital(),
+            AsciiBytesToBoolPair::new_x(),
+            AsciiBytesToBoolPair::new_x_capital(),
+        ]
+    }
+
+    #[test]
+    fn to_bytes_round_trips_for_builtin_pairs() {
+        for pair in builtin_pairs() {
+            assert_ne!(pair.true_value, pair.false_value);
+            for value in [true, false] {
+                assert_eq!(pair.convert(pair.to_bytes(value)).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn convert_canonicalize_emits_stored_tokens() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+        let mut out: Vec<u8> = vec![];
+
+        for input in [&b"YES"[..], b" no", b"maybe", b"Yes\n"] {
+            let _ = pair.convert_canonicalize(input, |token| {
+                out.extend_from_slice(token);
+                out.push(b',');
+            });
+        }
+        assert_eq!(out, b"yes,no,yes,");
+
+        assert!(!pair.convert_canonicalize(b"NO", |_| {}).unwrap());
+        assert!(pair.convert_canonicalize(b"maybe", |_| {}).is_err());
+    }
+
+    #[test]
+    fn convert_normalized_trims_and_ignores_case() {
+        let pair = AsciiBytesToBoolPair::default();
+        assert!(pair.convert_normalized(b"  TRUE  ").unwrap());
+        assert!(!pair.convert_normalized(b"False\n").unwrap());
+
+        let pair = AsciiBytesToBoolPair::new_yes_no_capitalised();
+        assert!(pair.convert_normalized(b"\tYes\n").unwrap());
+        assert!(!pair.convert_normalized(b" no ").unwrap());
+
+        let err = pair.convert_normalized(b" maybe ").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn builtin_bool_style_parses_from_str() {
+        assert_eq!(
+            "true_false".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::TrueFalse
+        );
+        assert_eq!(
+            "yes_no".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::YesNo
+        );
+        assert_eq!(
+            "on_off".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::OnOff
+        );
+        assert_eq!(
+            "y_n".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::YN
+        );
+        assert_eq!(
+            "t_f".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::TF
+        );
+        assert_eq!(
+            "o_x".parse::<BuiltinBoolStyle>().unwrap(),
+            BuiltinBoolStyle::OX
+        );
+
+        let err = "maybe".parse::<BuiltinBoolStyle>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn builtin_bool_style_to_pair_converts_correctly() {
+        let pair = BuiltinBoolStyle::OnOff.to_pair();
+        assert!(pair.convert(b"on").unwrap());
+        assert!(!pair.convert(b"off").unwrap());
+
+        let pair = BuiltinBoolStyle::YN.to_pair();
+        assert!(pair.convert(b"y").unwrap());
+        assert!(!pair.convert(b"n").unwrap());
+
+        let pair = BuiltinBoolStyle::TrueFalse.to_pair();
+        assert!(pair.convert(b"true").unwrap());
+        assert!(!pair.convert(b"false").unwrap());
+    }
+
+    #[test]
+    fn prefix_match_converter_matches_prefixes() {
+        let conv = PrefixMatchConverter::new(b"enable", b"disable");
+
+        assert!(conv.convert(b"enabled").unwrap());
+        assert!(conv.convert(b"ENABLE").unwrap());
+        assert!(!conv.convert(b"disabled").unwrap());
+        assert!(!conv.convert(b"Disable-all").unwrap());
+
+        let err = conv.convert(b"enab").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err_msg(&err).contains("Invalid boolean representation"));
+    }
+
+    #[test]
+    fn prefix_match_converter_rejects_ambiguous_input() {
+        let conv = PrefixMatchConverter::new(b"on", b"o");
+
+        assert!(!conv.convert(b"off").unwrap());
+
+        let err = conv.convert(b"online").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err_msg(&err).contains("Ambiguous boolean representation"));
+
+        let conv = conv.with_error::<Str2BoolError>();
+        assert_eq!(
+            conv.convert(b"online").unwrap_err(),
+            Str2BoolError::Ambiguous(b"online".to_vec())
+        );
+        assert_eq!(
+            conv.convert(b"x").unwrap_err(),
+            Str2BoolError::InvalidBytes(b"x".to_vec())
+        );
+    }
+
+    #[test]
+    fn to_string_lossy_returns_canonical_token() {
+        let pair = AsciiBytesToBoolPair::default();
+        assert_eq!(pair.to_string_lossy(true), "true");
+        assert_eq!(pair.to_string_lossy(false), "false");
+
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        assert_eq!(pair.to_string_lossy(true), "on");
+        assert_eq!(pair.to_string_lossy(false), "off");
+    }
+
+    #[test]
+    fn convert_to_u8_returns_zero_or_one() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        assert_eq!(pair.convert_to_u8(b"yes").unwrap(), 1);
+        assert_eq!(pair.convert_to_u8(b"no").unwrap(), 0);
+        assert!(pair.convert_to_u8(b"maybe").is_err());
+    }
+
+    #[test]
+    fn convert_fixed_strips_padding() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_fixed(b"true    ", b' ').unwrap());
+        assert!(!pair.convert_fixed(b"  false ", b' ').unwrap());
+        assert!(pair.convert_fixed(b"0000true", b'0').unwrap());
+        assert!(pair.convert_fixed(b"true    ", b'0').is_err());
+
+        let pair = AsciiBytesToBoolPair::new_o();
+        assert!(!pair.convert_fixed(b"    ", b' ').unwrap());
+    }
+
+    #[test]
+    fn convert_bounded_rejects_long_input() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_bounded(b"true", 5).unwrap());
+        assert!(!pair.convert_bounded(b"false", 5).unwrap());
+
+        let long = vec![b'a'; 1 << 20];
+        let err = pair.convert_bounded(&long, 5).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err_msg(&err).contains("too long"));
+    }
+
+    #[test]
+    fn convert_split_converts_each_segment() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        let results: Vec<bool> = pair
+            .convert_split(b"true;false;true", b';')
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, vec![true, false, true]);
+
+        let results: Vec<bool> = pair
+            .convert_split(b"false\ttrue", b'\t')
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, vec![false, true]);
+
+        let results: Vec<_> = pair.convert_split(b"true;yes", b';').collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].as_ref().unwrap());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn same_vocabulary_ignores_construction_path() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert!(pair.same_vocabulary(&AsciiBytesToBoolPair::default()));
+        assert!(pair.same_vocabulary(&AsciiBytesToBoolPair::new_custom(b"true", b"false")));
+        assert!(
+            AsciiBytesToBoolPair::new_o()
+                .same_vocabulary(&AsciiBytesToBoolPair::new_from_true_value(b"o"))
+        );
+
+        assert!(!pair.same_vocabulary(&AsciiBytesToBoolPair::new_yes_no()));
+        assert!(!pair.same_vocabulary(&AsciiBytesToBoolPair::new_true_false_capitalised()));
+    }
+
+    #[test]
+    fn convert_os_converts_utf8_os_str() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_os(OsStr::new("true")).unwrap());
+        assert!(!pair.convert_os(OsStr::new("false")).unwrap());
+
+        let err = pair.convert_os(OsStr::new("maybe")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn convert_os_rejects_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let pair = AsciiBytesToBoolPair::default();
+        let err = pair.convert_os(OsStr::from_bytes(b"tr\xffe")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn classify_returns_two_bit_category() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(pair.classify(b"false"), 0);
+        assert_eq!(pair.classify(b"true"), 1);
+        assert_eq!(pair.classify(b"maybe"), 2);
+    }
+
+    #[test]
+    fn longest_builtin_matches_builtin_pairs() {
+        let longest: usize = builtin_pairs()
+            .iter()
+            .flat_map(|p| [p.true_value.len(), p.false_value.len()])
+            .max()
+            .unwrap();
+        assert_eq!(longest, AsciiBytesToBoolPair::LONGEST_BUILTIN);
+    }
+
+    #[cfg(feature = "log")]
+    mod logged {
+        use super::*;
+        use std::sync::Mutex;
+
+        static LINES: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+        struct TestLogger;
+
+        impl log::Log for TestLogger {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                LINES.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: TestLogger = TestLogger;
+
+        #[test]
+        fn convert_or_default_logged_logs_field() {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Warn);
+
+            let pair = AsciiBytesToBoolPair::new_on_off();
+
+            assert!(!pair.convert_or_default_logged(b"off", true, "verbose"));
+            assert!(LINES.lock().unwrap().is_empty());
+
+            assert!(pair.convert_or_default_logged(b"maybe", true, "verbose"));
+            let lines = LINES.lock().unwrap();
+            assert_eq!(lines.len(), 1);
+            assert!(lines[0].contains("field=verbose"));
+            assert!(lines[0].contains("default=true"));
+        }
+    }
+
+    #[test]
+    fn convert_bracket_list_parses_list() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(
+            pair.convert_bracket_list(b"[true, false]").unwrap(),
+            vec![true, false]
+        );
+        assert_eq!(
+            pair.convert_bracket_list(b" [ false ,true,true ] ")
+                .unwrap(),
+            vec![false, true, true]
+        );
+        assert!(pair.convert_bracket_list(b"[]").unwrap().is_empty());
+
+        let err = pair.convert_bracket_list(b"[true, yes]").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn convert_bracket_list_rejects_missing_brackets() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        let err = pair.convert_bracket_list(b"true, false").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err_msg(&err).contains("Malformed"));
+
+        assert!(pair.convert_bracket_list(b"[true, false").is_err());
+    }
+
+    #[test]
+    fn validate_all_reports_every_invalid_token() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        let inputs: [&[u8]; 3] = [b"yes", b"no", b"yes"];
+        assert_eq!(pair.validate_all(inputs), Ok(vec![true, false, true]));
+
+        let inputs: [&[u8]; 5] = [b"yes", b"maybe", b"no", b"", b"YES"];
+        assert_eq!(
+            pair.validate_all(inputs),
+            Err(vec![
+                (1, b"maybe".to_vec()),
+                (3, b"".to_vec()),
+                (4, b"YES".to_vec()),
+            ])
+        );
+    }
+
+    #[test]
+    fn summarize_counts_each_category() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+        let inputs: [&[u8]; 5] = [b"yes", b"no", b"yes", b"maybe", b"yes"];
+
+        assert_eq!(
+            pair.summarize(inputs),
+            "parsed 5 tokens: 3 true, 1 false, 1 invalid"
+        );
+        assert_eq!(
+            pair.summarize([]),
+            "parsed 0 tokens: 0 true, 0 false, 0 invalid"
+        );
+    }
+
+    #[test]
+    fn convert_with_maybe_maps_maybe_token() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        assert!(!pair.convert_with_maybe(b"maybe", b"maybe", false).unwrap());
+        assert!(pair.convert_with_maybe(b"maybe", b"maybe", true).unwrap());
+        assert!(pair.convert_with_maybe(b"yes", b"maybe", false).unwrap());
+        assert!(!pair.convert_with_maybe(b"no", b"maybe", true).unwrap());
+        assert!(pair.convert_with_maybe(b"Maybe", b"maybe", true).is_err());
+    }
+
+    #[test]
+    fn is_open_detects_single_value_pairs() {
+        assert!(AsciiBytesToBoolPair::new_o().is_open());
+        assert!(AsciiBytesToBoolPair::new_from_true_value(b"on").is_open());
+        assert!(!AsciiBytesToBoolPair::new_yes_no().is_open());
+        assert!(!AsciiBytesToBoolPair::default().is_open());
+    }
+
+    #[test]
+    fn values_returns_true_then_false() {
+        let (t, f) = AsciiBytesToBoolPair::new_yes_no().values();
+        assert_eq!((t, f), (&b"yes"[..], &b"no"[..]));
+
+        let pair = AsciiBytesToBoolPair::default();
+        assert_eq!(pair.values(), (&b"true"[..], &b"false"[..]));
+
+        let pair = AsciiBytesToBoolPair::new_from_true_value(b"on");
+        assert_eq!(pair.values(), (&b"on"[..], &b""[..]));
+    }
+
+    #[test]
+    fn as_entries_lists_tokens_with_values() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+        let expected: Vec<(&[u8], bool)> = vec![(b"yes", true), (b"no", false)];
+        assert_eq!(pair.as_entries(), expected);
+
+        for (token, value) in pair.as_entries() {
+            assert_eq!(pair.convert(token).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn convert_cstr_truncates_at_nul() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_cstr(b"true\0\0").unwrap());
+        assert!(!pair.convert_cstr(b"false\0garbage").unwrap());
+        assert!(pair.convert_cstr(b"true").unwrap());
+        assert!(pair.convert_cstr(b"\0true").is_err());
+    }
+
+    #[test]
+    fn to_shell_arg_quotes_when_needed() {
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        assert_eq!(pair.to_shell_arg(true), "on");
+        assert_eq!(pair.to_shell_arg(false), "off");
+
+        let pair = AsciiBytesToBoolPair::new_custom(b"turned on", b"it's off");
+        assert_eq!(pair.to_shell_arg(true), "'turned on'");
+        assert_eq!(pair.to_shell_arg(false), "'it'\\''s off'");
+
+        let pair = AsciiBytesToBoolPair::new_o();
+        assert_eq!(pair.to_shell_arg(false), "''");
+    }
+
+    #[test]
+    fn case_style_tolerant_converter_matches_styles() {
+        let conv = CaseStyleTolerantConverter::new(b"always_on", b"always_off");
+
+        assert!(conv.convert(b"always_on").unwrap());
+        assert!(conv.convert(b"ALWAYS-ON").unwrap());
+        assert!(conv.convert(b"Always On").unwrap());
+        assert!(!conv.convert(b"Always-Off").unwrap());
+
+        assert!(conv.convert(b"alwayson").is_err());
+        assert!(conv.convert(b" always_on").is_err());
+
+        let conv = conv.with_error::<Str2BoolError>();
+        assert_eq!(
+            conv.convert(b"always").unwrap_err(),
+            Str2BoolError::InvalidBytes(b"always".to_vec())
+        );
+    }
+
+    #[test]
+    fn opposite_token_returns_other_token() {
+        let pair = AsciiBytesToBoolPair::new_on_off();
+
+        assert_eq!(pair.opposite_token(true), b"off");
+        assert_eq!(pair.opposite_token(false), b"on");
+    }
+
+    #[test]
+    fn convert_scored_is_certain_for_exact_matches() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(pair.convert_scored(b"true").unwrap(), (true, 1.0));
+        assert_eq!(pair.convert_scored(b"false").unwrap(), (false, 1.0));
+        assert!(pair.convert_scored(b"treu").is_err());
+    }
+
+    #[test]
+    fn convert_recoverable_returns_raw_input_on_failure() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(pair.convert_recoverable(b"true"), Ok(true));
+        assert_eq!(pair.convert_recoverable(b"false"), Ok(false));
+
+        let input: &[u8] = b"maybe";
+        let raw = pair.convert_recoverable(input).unwrap_err();
+        assert_eq!(raw, b"maybe");
+        assert!(std::ptr::eq(raw, input));
+    }
+
+    #[test]
+    fn convert_trim_matches_strips_trim_set() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(
+            pair.convert_trim_matches(b" \t\"true\" ", b" \t\"")
+                .unwrap()
+        );
+        assert!(!pair.convert_trim_matches(b"'false'", b"'").unwrap());
+        assert!(pair.convert_trim_matches(b"true", b"").unwrap());
+        assert!(pair.convert_trim_matches(b" \"true\" ", b" ").is_err());
+    }
+
+    #[test]
+    fn is_canonical_requires_exact_match() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert!(pair.is_canonical(b"true"));
+        assert!(pair.is_canonical(b"false"));
+        assert!(!pair.is_canonical(b"TRUE"));
+        assert!(!pair.is_canonical(b" true"));
+
+        let pair = AsciiBytesToBoolPair::new_x();
+        assert!(pair.is_canonical(b""));
+        assert!(!pair.is_canonical(b"  "));
+    }
+
+    #[test]
+    fn convert_consume_returns_remaining_bytes() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(
+            pair.convert_consume(b"trueREST").unwrap(),
+            (true, &b"REST"[..])
+        );
+        assert_eq!(pair.convert_consume(b"false").unwrap(), (false, &b""[..]));
+        assert!(pair.convert_consume(b"tru").is_err());
+
+        let pair = AsciiBytesToBoolPair::new_o();
+        assert_eq!(pair.convert_consume(b"oo").unwrap(), (true, &b"o"[..]));
+        assert!(pair.convert_consume(b"x").is_err());
+        assert!(pair.convert_consume(b"garbage").is_err());
+        assert!(pair.convert_consume(b"").is_err());
+    }
+
+    #[test]
+    fn to_bit_char_ignores_tokens() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        assert_eq!(pair.to_bit_char(true), '1');
+        assert_eq!(pair.to_bit_char(false), '0');
+    }
+
+    #[test]
+    fn parse_flag_set_deduplicates_trimmed_tokens() {
+        let flags = parse_flag_set(b"alpha, beta ,alpha");
+        let expected: HashSet<Vec<u8>> = [b"alpha".to_vec(), b"beta".to_vec()].into();
+        assert_eq!(flags, expected);
+
+        assert!(parse_flag_set(b"").is_empty());
+        assert!(parse_flag_set(b" , ,").is_empty());
+    }
+
+    #[test]
+    fn key_present_scans_query_keys() {
+        assert!(key_present(b"?verbose&debug", b"verbose"));
+        assert!(key_present(b"?verbose&debug", b"debug"));
+        assert!(key_present(b"level=3&debug=", b"level"));
+
+        assert!(!key_present(b"?verbose&debug", b"verb"));
+        assert!(!key_present(b"?verbose&debug", b"quiet"));
+        assert!(!key_present(b"level=debug", b"debug"));
+        assert!(!key_present(b"", b""));
+    }
+
+    #[test]
+    fn convert_progressive_falls_back_to_lenient_stages() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert!(pair.convert_progressive(b"true").unwrap());
+        assert!(!pair.convert_progressive(b" false\n").unwrap());
+        assert!(pair.convert_progressive(b"TRUE").unwrap());
+        assert!(!pair.convert_progressive(b"\tFaLsE ").unwrap());
+
+        let err = pair.convert_progressive(b" maybe ").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn classify_fields_converts_fixed_width_fields() {
+        let pair = AsciiBytesToBoolPair::new_custom(b"TRUE", b"FALS");
+
+        let values: Vec<bool> = pair
+            .classify_fields(b"TRUEFALS", 4)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(values, vec![true, false]);
+
+        let results: Vec<_> = pair.classify_fields(b"TRUENOPE", 4).collect();
+        assert!(results[0].as_ref().unwrap());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn classify_fields_rejects_misaligned_buffer() {
+        let pair = AsciiBytesToBoolPair::new_custom(b"TRUE", b"FALS");
+
+        let results: Vec<_> = pair.classify_fields(b"TRUEFAL", 4).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].as_ref().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+
+        let results: Vec<_> = pair.classify_fields(b"TRUE", 0).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn accepted_strings_lists_tokens() {
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        assert_eq!(pair.accepted_strings(), vec!["on", "off"]);
+    }
+
+    #[test]
+    fn convert_cmp_key_sorts_false_first() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        assert_eq!(pair.convert_cmp_key(b"no").unwrap(), 0);
+        assert_eq!(pair.convert_cmp_key(b"yes").unwrap(), 1);
+        assert!(pair.convert_cmp_key(b"maybe").is_err());
+
+        let mut rows: Vec<&[u8]> = vec![b"yes", b"no", b"yes", b"no"];
+        rows.sort_by_key(|r| pair.convert_cmp_key(r).unwrap());
+        let expected: Vec<&[u8]> = vec![b"no", b"no", b"yes", b"yes"];
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn vocabulary_hash_is_stable_for_equivalent_pairs() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        assert_eq!(
+            pair.vocabulary_hash(),
+            AsciiBytesToBoolPair::default().vocabulary_hash()
+        );
+        assert_eq!(
+            pair.vocabulary_hash(),
+            AsciiBytesToBoolPair::new_custom(b"true", b"false").vocabulary_hash()
+        );
+
+        assert_ne!(
+            pair.vocabulary_hash(),
+            AsciiBytesToBoolPair::new_yes_no().vocabulary_hash()
+        );
+        assert_ne!(
+            pair.vocabulary_hash(),
+            AsciiBytesToBoolPair::new_custom(b"false", b"true").vocabulary_hash()
+        );
+    }
+
+    #[test]
+    fn vocabulary_hash_agrees_with_same_vocabulary() {
+        let mut pairs = builtin_pairs();
+        pairs.push(AsciiBytesToBoolPair::new_from_true_value(b"x"));
+        pairs.push(AsciiBytesToBoolPair::new_custom(b"on", b"off"));
+
+        for a in &pairs {
+            for b in &pairs {
+                assert_eq!(
+                    a.same_vocabulary(b),
+                    a.vocabulary_hash() == b.vocabulary_hash(),
+                    "{:?} vs {:?}",
+                    a.to_spec(),
+                    b.to_spec(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn convert_with_flags_marks_case_folded_match() {
+        let pair = AsciiBytesToBoolPair::new_true_false();
+
+        let (b, flags) = pair.convert_with_flags(b"true").unwrap();
+        assert!(b);
+        assert_eq!(flags, ConvertFlags::default());
+
+        let (b, flags) = pair.convert_with_flags(b"TRUE").unwrap();
+        assert!(b);
+        assert!(flags.case_folded);
+        assert!(!flags.trimmed);
+
+        let (b, flags) = pair.convert_with_flags(b" False ").unwrap();
+        assert!(!b);
+        assert!(flags.case_folded);
+        assert!(flags.trimmed);
+
+        assert!(pair.convert_with_flags(b"maybe").is_err());
+    }
+
+    #[test]
+    fn convert_first_line_ignores_following_lines() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_first_line(b"true\nignored").unwrap());
+        assert!(!pair.convert_first_line(b"false\r\ntrue\r\n").unwrap());
+        assert!(pair.convert_first_line(b"true").unwrap());
+        assert!(pair.convert_first_line(b"\ntrue").is_err());
+    }
+
+    #[test]
+    fn convert_canonical_token_is_non_empty() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+
+        let (b, token) = pair.convert_canonical_token(b"yes").unwrap();
+        assert!(b);
+        assert_eq!(token.as_bytes(), b"yes");
+
+        let (b, token) = pair.convert_canonical_token(b"no").unwrap();
+        assert!(!b);
+        assert_eq!(token.as_bytes(), b"no");
+
+        let pair = AsciiBytesToBoolPair::new_o();
+        let (b, token) = pair.convert_canonical_token(b"o").unwrap();
+        assert!(b);
+        assert_eq!(token.as_bytes(), b"o");
+
+        let err = pair.convert_canonical_token(b"").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        assert!(CanonicalToken::new(b"").is_none());
+    }
+
+    #[test]
+    fn convert_percent_decoded_decodes_escapes() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_percent_decoded(b"%74rue").unwrap());
+        assert!(!pair.convert_percent_decoded(b"%66%61%6C%73%65").unwrap());
+        assert!(pair.convert_percent_decoded(b"true").unwrap());
+
+        let err = pair.convert_percent_decoded(b"%zz").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(pair.convert_percent_decoded(b"true%7").is_err());
+        assert!(pair.convert_percent_decoded(b"%74%72%75").is_err());
+    }
+
+    #[test]
+    fn convert_truthy_strict_classifies_each_category() {
+        let pair = AsciiBytesToBoolPair::default();
+        let false_values: [&[u8]; 2] = [b"0", b""];
+        let invalid_values: [&[u8]; 2] = [b"null", b"0"];
+
+        assert!(
+            pair.convert_truthy_strict(b"1", &false_values, &invalid_values)
+                .unwrap()
+        );
+        assert!(
+            pair.convert_truthy_strict(b"anything", &false_values, &invalid_values)
+                .unwrap()
+        );
+        assert!(
+            pair.convert_truthy_strict(b"true", &false_values, &invalid_values)
+                .unwrap()
+        );
+
+        assert!(
+            !pair
+                .convert_truthy_strict(b"", &false_values, &invalid_values)
+                .unwrap()
+        );
+        assert!(
+            !pair
+                .convert_truthy_strict(b"false", &false_values, &invalid_values)
+                .unwrap()
+        );
+
+        let err = pair
+            .convert_truthy_strict(b"null", &false_values, &invalid_values)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(
+            pair.convert_truthy_strict(b"0", &false_values, &invalid_values)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn convert_hex_unescape_resolves_escapes() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_hex_unescape(b"\\x74rue").unwrap());
+        assert!(!pair.convert_hex_unescape(b"\\x66\\x61lse").unwrap());
+        assert!(pair.convert_hex_unescape(b"true").unwrap());
+
+        let err = pair.convert_hex_unescape(b"\\xzzrue").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(pair.convert_hex_unescape(b"true\\x7").is_err());
+        assert!(pair.convert_hex_unescape(b"\\t\\x72ue").is_err());
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn convert_base64_decodes_then_converts() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_base64(b"dHJ1ZQ==").unwrap());
+        assert!(!pair.convert_base64(b"ZmFsc2U=").unwrap());
+
+        let err = pair.convert_base64(b"dHJ1ZQ=!").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let err = pair.convert_base64(b"bWF5YmU=").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance(b"true", b"true"), 0);
+        assert_eq!(edit_distance(b"treu", b"true"), 1);
+        assert_eq!(edit_distance(b"tru", b"true"), 1);
+        assert_eq!(edit_distance(b"", b"yes"), 3);
+        assert_eq!(edit_distance(b"maybe", b"true"), 4);
+        assert_eq!(edit_distance(b"abcdef", b"badcfe"), 3);
+        assert_eq!(edit_distance(&[b'x'; 1000], b"true"), 1000);
+    }
+
+    #[test]
+    fn convert_diff_reports_divergence_index() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_diff(b"true").unwrap());
+        assert!(!pair.convert_diff(b"false").unwrap());
+
+        let (err, at) = pair.convert_diff(b"trae").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(at, 2);
+
+        let (_, at) = pair.convert_diff(b"falsy").unwrap_err();
+        assert_eq!(at, 4);
+
+        let (_, at) = pair.convert_diff(b"yes").unwrap_err();
+        assert_eq!(at, 3);
+
+        let pair = AsciiBytesToBoolPair::new_x();
+        assert!(!pair.convert_diff(b"").unwrap());
+        assert_eq!(pair.convert_diff(b"y").unwrap_err().1, 0);
+    }
+
+    #[test]
+    fn convert_na_maps_na_tokens_to_none() {
+        let pair = AsciiBytesToBoolPair::default();
+        let na: &[&[u8]] = &[b"n/a", b"unknown"];
+
+        assert_eq!(pair.convert_na(b"N/A", na).unwrap(), None);
+        assert_eq!(pair.convert_na(b"Unknown", na).unwrap(), None);
+        assert_eq!(pair.convert_na(b"true", na).unwrap(), Some(true));
+        assert_eq!(pair.convert_na(b"false", na).unwrap(), Some(false));
+
+        let err = pair.convert_na(b"garbage", na).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn convert_map_maps_into_domain_enum() {
+        #[derive(Debug, PartialEq)]
+        enum Status {
+            On,
+            Off,
+        }
+
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        let to_status = |b: bool| if b { Status::On } else { Status::Off };
+
+        assert_eq!(pair.convert_map(b"on", to_status).unwrap(), Status::On);
+        assert_eq!(pair.convert_map(b"off", to_status).unwrap(), Status::Off);
+        assert!(pair.convert_map(b"maybe", to_status).is_err());
+    }
+
+    #[test]
+    fn convert_with_cost_counts_compared_bytes() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        let (res, cost) = pair.convert_with_cost(b"true");
+        assert!(res.unwrap());
+        assert_eq!(cost, 4);
+
+        let (res, cost) = pair.convert_with_cost(b"false");
+        assert!(!res.unwrap());
+        assert_eq!(cost, 5);
+
+        let (res, cost) = pair.convert_with_cost(b"trap");
+        assert!(res.is_err());
+        assert_eq!(cost, 3);
+
+        let (res, cost) = pair.convert_with_cost(b"maybe!");
+        assert!(res.is_err());
+        assert_eq!(cost, 0);
+
+        let (res, cost) = AsciiBytesToBoolPair::new_x().convert_with_cost(b"");
+        assert!(!res.unwrap());
+        assert_eq!(cost, 0);
+    }
+
+    #[test]
+    fn convert_many_or_substitutes_default() {
+        let pair = AsciiBytesToBoolPair::new_yes_no();
+        let inputs: [&[u8]; 4] = [b"yes", b"maybe", b"no", b""];
+
+        assert_eq!(
+            pair.convert_many_or(inputs, true),
+            vec![true, true, false, true]
+        );
+        assert_eq!(
+            pair.convert_many_or(inputs, false),
+            vec![true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn convert_cow_borrows_common_message() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert_eq!(pair.convert_cow(b"true"), Ok(true));
+        assert_eq!(pair.convert_cow(b"false"), Ok(false));
+
+        let err = pair.convert_cow(b"maybe").unwrap_err();
+        assert!(matches!(err, Cow::Borrowed(_)));
+        assert_eq!(err, "Invalid boolean representation");
+
+        let err = pair.convert_cow(b"\xff\xfe").unwrap_err();
+        assert!(matches!(err, Cow::Owned(_)));
+        assert_eq!(err, "Non-ASCII boolean representation: [ff, fe]");
+    }
+
+    #[test]
+    fn convert_strip_comment_drops_trailing_comment() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(
+            pair.convert_strip_comment(b"true  # enable feature", b'#')
+                .unwrap()
+        );
+        assert!(!pair.convert_strip_comment(b"false;note", b';').unwrap());
+        assert!(pair.convert_strip_comment(b" true ", b'#').unwrap());
+        assert!(pair.convert_strip_comment(b"# true", b'#').is_err());
+    }
+
+    #[test]
+    fn match_bool_matches_tokens() {
+        assert_eq!(match_bool(b"yes", b"yes", b"no"), Some(true));
+        assert_eq!(match_bool(b"no", b"yes", b"no"), Some(false));
+        assert_eq!(match_bool(b"maybe", b"yes", b"no"), None);
+
+        assert_eq!(match_bool(b"", b"yes", b"no"), None);
+        assert_eq!(match_bool(b"", b"o", b""), Some(false));
+        assert_eq!(match_bool(b"", b"", b""), Some(true));
+    }
+
+    #[test]
+    fn length_converter_classifies_by_length() {
+        let conv = LengthConverter::new(2, 1);
+
+        assert!(conv.convert(b"yy").unwrap());
+        assert!(conv.convert(b"ab").unwrap());
+        assert!(!conv.convert(b"y").unwrap());
+        assert!(conv.convert(b"").is_err());
+        assert!(conv.convert(b"yyy").is_err());
+
+        let conv = LengthConverter::new(1, 1);
+        let err = conv.convert(b"y").unwrap_err();
+        assert!(err_msg(&err).contains("Ambiguous"));
+
+        let conv = conv.with_error::<Str2BoolError>();
+        assert_eq!(
+            conv.convert(b"y").unwrap_err(),
+            Str2BoolError::Ambiguous(b"y".to_vec())
+        );
+    }
+
+    #[test]
+    fn static_error_message_matches_convert() {
+        let pair = AsciiBytesToBoolPair::default();
+        let err = pair.convert(b"maybe").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            AsciiBytesToBoolPair::static_error_message()
+        );
+        assert_eq!(
+            pair.convert_cow(b"maybe").unwrap_err(),
+            AsciiBytesToBoolPair::static_error_message()
+        );
+    }
+
+    #[test]
+    fn convert_flag_typed_brands_the_result() {
+        struct Verbose;
+        struct DryRun;
+
+        fn run(verbose: Flag<Verbose>, dry_run: Flag<DryRun>) -> (bool, bool) {
+            (verbose.get(), dry_run.get())
+        }
+
+        let pair = AsciiBytesToBoolPair::new_on_off();
+        let verbose: Flag<Verbose> = pair.convert_flag_typed(b"on").unwrap();
+        let dry_run = pair.convert_flag_typed::<DryRun>(b"off").unwrap();
+
+        assert_eq!(run(verbose, dry_run), (true, false));
+        assert_eq!(verbose, Flag::new(true));
+        assert!(pair.convert_flag_typed::<Verbose>(b"maybe").is_err());
+    }
+
+    #[test]
+    fn convert_nul_delimited_splits_records() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        let results: Vec<bool> = pair
+            .convert_nul_delimited(b"true\0false\0")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, vec![true, false]);
+
+        let results: Vec<bool> = pair
+            .convert_nul_delimited(b"false\0true")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, vec![false, true]);
+
+        assert_eq!(pair.convert_nul_delimited(b"").count(), 0);
+        assert!(pair.convert_nul_delimited(b"true\0\0").any(|r| r.is_err()));
+    }
+
+    #[test]
+    fn convert_best_effort_leans_to_nearest_token() {
+        let pair = AsciiBytesToBoolPair::default();
+
+        assert!(pair.convert_best_effort(b"true"));
+        assert!(!pair.convert_best_effort(b"false"));
+        assert!(pair.convert_best_effort(b"ture"));
+        assert!(!pair.convert_best_effort(b"flase"));
+        assert!(!pair.convert_best_effort(b"xyzzy"));
+        assert!(pair.convert_best_effort(b"truetrue"));
+        assert!(!pair.convert_best_effort(&[b't'; 4 << 20]));
+    }
 }
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::io;
+
+use io::ErrorKind;
+
+use crate::ascii_bytes::AsciiBytesToBool;
+use crate::error::INVALID_BOOLEAN_REPRESENTATION;
+
+#[derive(Debug, Clone, Default)]
+pub struct AsciiBytesToBoolMap {
+    pub map: HashMap<Vec<u8>, bool>,
+}
+
+impl AsciiBytesToBoolMap {
+    pub fn new(map: HashMap<Vec<u8>, bool>) -> Self {
+        Self { map }
+    }
+
+    pub fn insert_true(mut self, token: &[u8]) -> Self {
+        self.map.insert(token.to_vec(), true);
+        self
+    }
+
+    pub fn insert_false(mut self, token: &[u8]) -> Self {
+        self.map.insert(token.to_vec(), false);
+        self
+    }
+}
+
+impl AsciiBytesToBool for AsciiBytesToBoolMap {
+    type Error = io::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.map
+            .get(input)
+            .copied()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, INVALID_BOOLEAN_REPRESENTATION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_methods_insert_tokens() {
+        let map = AsciiBytesToBoolMap::default()
+            .insert_true(b"yes")
+            .insert_false(b"no");
+
+        assert!(map.convert(b"yes").unwrap());
+        assert!(!map.convert(b"no").unwrap());
+    }
+
+    #[test]
+    fn many_entries_convert_correctly() {
+        let mut map = AsciiBytesToBoolMap::default();
+        for i in 0..50 {
+            map = map
+                .insert_true(format!("on{}", i).as_bytes())
+                .insert_false(format!("off{}", i).as_bytes());
+        }
+        assert_eq!(map.map.len(), 100);
+
+        for i in 0..50 {
+            assert!(map.convert(format!("on{}", i).as_bytes()).unwrap());
+            assert!(!map.convert(format!("off{}", i).as_bytes()).unwrap());
+        }
+    }
+
+    #[test]
+    fn absent_key_returns_error() {
+        let map = AsciiBytesToBoolMap::default().insert_true(b"yes");
+
+        let err = map.convert(b"maybe").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
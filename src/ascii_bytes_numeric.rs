@@ -0,0 +1,97 @@
+use std::io;
+
+use io::ErrorKind;
+
+use crate::ascii_bytes::AsciiBytesToBool;
+use crate::error::INVALID_BOOLEAN_REPRESENTATION;
+
+/// Parses base-10 digits as a boolean: zero is false and any other value is true.
+///
+/// Leading zeros are ignored, so `000` is false and `007` is true. Signs,
+/// whitespace and other radixes are rejected.
+///
+/// An optional `separator` (e.g. `_` or `,`) may appear between two digits,
+/// so `1_000` is true; it may not lead, trail or repeat.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsciiBytesToBoolNumeric {
+    pub separator: Option<u8>,
+}
+
+impl AsciiBytesToBoolNumeric {
+    pub fn new_with_separator(separator: u8) -> Self {
+        Self {
+            separator: Some(separator),
+        }
+    }
+
+    fn is_valid(&self, input: &[u8]) -> bool {
+        let mut groups = input.split(|b| Some(*b) == self.separator);
+        groups.all(|g| !g.is_empty() && g.iter().all(u8::is_ascii_digit))
+    }
+}
+
+impl AsciiBytesToBool for AsciiBytesToBoolNumeric {
+    type Error = io::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        if !self.is_valid(input) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                INVALID_BOOLEAN_REPRESENTATION,
+            ));
+        }
+        Ok(input.iter().any(|b| b.is_ascii_digit() && *b != b'0'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_and_nonzero_convert_correctly() {
+        let conv = AsciiBytesToBoolNumeric::default();
+
+        assert!(!conv.convert(b"0").unwrap());
+        assert!(conv.convert(b"1").unwrap());
+        assert!(conv.convert(b"42").unwrap());
+    }
+
+    #[test]
+    fn leading_zeros_are_ignored() {
+        let conv = AsciiBytesToBoolNumeric::default();
+
+        assert!(!conv.convert(b"000").unwrap());
+        assert!(!conv.convert(b"00").unwrap());
+        assert!(conv.convert(b"007").unwrap());
+        assert!(conv.convert(b"00000000000000000000000000000001").unwrap());
+    }
+
+    #[test]
+    fn non_digits_are_rejected() {
+        let conv = AsciiBytesToBoolNumeric::default();
+
+        for input in [&b""[..], b"-1", b" 1", b"0x1", b"1.0"] {
+            let err = conv.convert(input).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn separator_between_digits_is_accepted() {
+        let conv = AsciiBytesToBoolNumeric::new_with_separator(b'_');
+        assert!(conv.convert(b"1_000").unwrap());
+        assert!(!conv.convert(b"0").unwrap());
+        assert!(!conv.convert(b"0_000").unwrap());
+        assert!(conv.convert(b"_1").is_err());
+        assert!(conv.convert(b"1_").is_err());
+        assert!(conv.convert(b"1__0").is_err());
+        assert!(conv.convert(b"1,000").is_err());
+
+        let conv = AsciiBytesToBoolNumeric::new_with_separator(b',');
+        assert!(conv.convert(b"1,000").unwrap());
+
+        let conv = AsciiBytesToBoolNumeric::default();
+        assert!(conv.convert(b"1_000").is_err());
+    }
+}
@@ -0,0 +1,313 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use crate::ascii_bytes::AsciiBytesToBool;
+use crate::error::{BoolParseError, Str2BoolError};
+
+pub struct AsciiBytesToBoolOwned<E = io::Error> {
+    pub true_value: Vec<u8>,
+    pub false_value: Vec<u8>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> fmt::Debug for AsciiBytesToBoolOwned<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsciiBytesToBoolOwned")
+            .field("true_value", &self.true_value)
+            .field("false_value", &self.false_value)
+            .finish()
+    }
+}
+
+impl<E> Clone for AsciiBytesToBoolOwned<E> {
+    fn clone(&self) -> Self {
+        Self {
+            true_value: self.true_value.clone(),
+            false_value: self.false_value.clone(),
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> PartialEq for AsciiBytesToBoolOwned<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.true_value == other.true_value && self.false_value == other.false_value
+    }
+}
+
+impl<E> Eq for AsciiBytesToBoolOwned<E> {}
+
+impl AsciiBytesToBoolOwned {
+    pub fn new_custom(true_value: Vec<u8>, false_value: Vec<u8>) -> Self {
+        Self {
+            true_value,
+            false_value,
+            error: PhantomData,
+        }
+    }
+
+    /// Like [`Self::new_custom`], but rejects non-ASCII tokens, reporting
+    /// the first offending one as [`Str2BoolError::NonAscii`].
+    ///
+    /// The check happens only here; the fields stay public and are not
+    /// re-validated afterwards.
+    pub fn new_ascii_checked(
+        true_value: Vec<u8>,
+        false_value: Vec<u8>,
+    ) -> Result<Self, Str2BoolError> {
+        if let Some(token) = [&true_value, &false_value]
+            .into_iter()
+            .find(|t| !t.is_ascii())
+        {
+            return Err(Str2BoolError::NonAscii(token.clone()));
+        }
+        Ok(Self::new_custom(true_value, false_value))
+    }
+
+    /// Parses a `true_value:false_value` spec, splitting at the first `:`.
+    pub fn parse_spec(spec: &str) -> Result<Self, Str2BoolError> {
+        let (t, f) = spec
+            .split_once(':')
+            .ok_or_else(|| Str2BoolError::malformed(spec.as_bytes()))?;
+        Ok(Self::new_custom(t.into(), f.into()))
+    }
+
+    /// Reads a `{ true = "...", false = "..." }` table.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_value(v: &toml::Value) -> Result<Self, Str2BoolError> {
+        let token = |key: &str| {
+            v.get(key)
+                .and_then(toml::Value::as_str)
+                .map(|s| s.as_bytes().to_vec())
+                .ok_or_else(|| Str2BoolError::malformed(v.to_string().as_bytes()))
+        };
+        Ok(Self::new_custom(token("true")?, token("false")?))
+    }
+
+    /// "はい" (`E3 81 AF E3 81 84`) is true, "いいえ" (`E3 81 84 E3 81 84 E3 81 88`) is false.
+    pub fn new_ja_hai_iie() -> Self {
+        Self::new_custom("はい".into(), "いいえ".into())
+    }
+
+    /// "sí" (`73 C3 AD`, with a precomposed `í`) is true, "no" is false.
+    pub fn new_es_si_no() -> Self {
+        Self::new_custom("sí".into(), "no".into())
+    }
+
+    pub fn new_es_activado_desactivado() -> Self {
+        Self::new_custom("activado".into(), "desactivado".into())
+    }
+}
+
+impl<E> AsciiBytesToBoolOwned<E> {
+    pub fn with_error<F>(self) -> AsciiBytesToBoolOwned<F> {
+        AsciiBytesToBoolOwned {
+            true_value: self.true_value,
+            false_value: self.false_value,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<E> AsciiBytesToBoolOwned<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    /// Normalizes `input` to NFC before matching, so a decomposed `é`
+    /// (`65 CC 81`) matches a precomposed (`C3 A9`) token.
+    ///
+    /// Requires the `unicode` feature, which pulls in `unicode-normalization`.
+    /// The stored tokens are compared as-is and should themselves be NFC.
+    #[cfg(feature = "unicode")]
+    pub fn convert_nfc(&self, input: &str) -> Result<bool, E> {
+        use unicode_normalization::UnicodeNormalization;
+
+        let normalized: String = input.nfc().collect();
+        self.convert(normalized.as_bytes())
+    }
+}
+
+impl<E> AsciiBytesToBool for AsciiBytesToBoolOwned<E>
+where
+    E: BoolParseError + std::error::Error,
+{
+    type Error = E;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        if input == self.true_value {
+            Ok(true)
+        } else if input == self.false_value {
+            Ok(false)
+        } else {
+            Err(E::invalid(input))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii_bytes::AsciiBytesToBoolPair;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn custom_owned_converts_correctly() {
+        let conv = AsciiBytesToBoolOwned::new_custom(b"ok".to_vec(), b"ng".to_vec());
+
+        assert!(conv.convert(b"ok").unwrap());
+        assert!(!conv.convert(b"ng").unwrap());
+
+        let err = conv.convert(b"maybe").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn ascii_checked_accepts_ascii_tokens() {
+        let conv =
+            AsciiBytesToBoolOwned::new_ascii_checked(b"yes".to_vec(), b"no".to_vec()).unwrap();
+
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(!conv.convert(b"no").unwrap());
+        assert!(conv.convert("ｙｅｓ".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn ascii_checked_rejects_non_ascii_tokens() {
+        let err =
+            AsciiBytesToBoolOwned::new_ascii_checked("はい".into(), b"no".to_vec()).unwrap_err();
+        assert_eq!(err, Str2BoolError::NonAscii("はい".into()));
+
+        let err = AsciiBytesToBoolOwned::new_ascii_checked(b"yes".to_vec(), b"n\xf6".to_vec())
+            .unwrap_err();
+        assert_eq!(err, Str2BoolError::NonAscii(b"n\xf6".to_vec()));
+    }
+
+    #[test]
+    fn ja_hai_iie_converts_correctly() {
+        let conv = AsciiBytesToBoolOwned::new_ja_hai_iie();
+
+        assert_eq!(conv.true_value, b"\xe3\x81\xaf\xe3\x81\x84");
+        assert_eq!(conv.false_value, b"\xe3\x81\x84\xe3\x81\x84\xe3\x81\x88");
+
+        assert!(conv.convert("はい".as_bytes()).unwrap());
+        assert!(!conv.convert("いいえ".as_bytes()).unwrap());
+        assert!(conv.convert(b"yes").is_err());
+    }
+
+    #[test]
+    fn with_error_distinguishes_non_ascii() {
+        let conv = AsciiBytesToBoolOwned::new_ja_hai_iie().with_error::<Str2BoolError>();
+
+        assert!(conv.convert("はい".as_bytes()).unwrap());
+        assert_eq!(
+            conv.convert("いい".as_bytes()).unwrap_err(),
+            Str2BoolError::NonAscii("いい".into())
+        );
+        assert_eq!(
+            conv.convert(b"yes").unwrap_err(),
+            Str2BoolError::InvalidBytes(b"yes".to_vec())
+        );
+    }
+
+    #[test]
+    fn es_si_no_converts_correctly() {
+        let conv = AsciiBytesToBoolOwned::new_es_si_no();
+
+        assert_eq!(conv.true_value, b"\x73\xc3\xad");
+
+        assert!(conv.convert("sí".as_bytes()).unwrap());
+        assert!(!conv.convert(b"no").unwrap());
+        assert!(conv.convert(b"si").is_err());
+    }
+
+    #[test]
+    fn es_activado_desactivado_converts_correctly() {
+        let conv = AsciiBytesToBoolOwned::new_es_activado_desactivado();
+
+        assert!(conv.convert(b"activado").unwrap());
+        assert!(!conv.convert(b"desactivado").unwrap());
+        assert!(conv.convert(b"Activado").is_err());
+    }
+
+    #[test]
+    fn parse_spec_splits_at_first_colon() {
+        let conv = AsciiBytesToBoolOwned::parse_spec("yes:no").unwrap();
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(!conv.convert(b"no").unwrap());
+
+        let conv = AsciiBytesToBoolOwned::parse_spec("o:").unwrap();
+        assert_eq!(conv.false_value, b"");
+
+        let err = AsciiBytesToBoolOwned::parse_spec("yes").unwrap_err();
+        assert_eq!(err, Str2BoolError::Malformed(b"yes".to_vec()));
+    }
+
+    #[test]
+    fn to_spec_round_trips_with_parse_spec() {
+        for pair in [
+            AsciiBytesToBoolPair::default(),
+            AsciiBytesToBoolPair::new_on_off_capitalised(),
+            AsciiBytesToBoolPair::new_x(),
+            AsciiBytesToBoolPair::new_custom(b"ok", b"a:b"),
+        ] {
+            let spec: String = pair.to_spec().unwrap();
+            let conv = AsciiBytesToBoolOwned::parse_spec(&spec).unwrap();
+            assert_eq!(conv.true_value, pair.true_value);
+            assert_eq!(conv.false_value, pair.false_value);
+        }
+
+        assert_eq!(
+            AsciiBytesToBoolPair::new_on_off().to_spec().as_deref(),
+            Some("on:off")
+        );
+    }
+
+    #[test]
+    fn to_spec_refuses_specs_that_do_not_round_trip() {
+        assert_eq!(
+            AsciiBytesToBoolPair::new_custom(b"a:b", b"c").to_spec(),
+            None
+        );
+        assert_eq!(
+            AsciiBytesToBoolPair::new_custom(b"\xff", b"n").to_spec(),
+            None
+        );
+        assert_eq!(
+            AsciiBytesToBoolPair::new_custom(b"y", b"\xfe").to_spec(),
+            None
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn convert_nfc_matches_decomposed_input() {
+        let conv = AsciiBytesToBoolOwned::new_custom("activé".into(), "désactivé".into());
+
+        assert!(conv.convert("active\u{301}".as_bytes()).is_err());
+        assert!(conv.convert_nfc("active\u{301}").unwrap());
+        assert!(!conv.convert_nfc("de\u{301}sactive\u{301}").unwrap());
+        assert!(conv.convert_nfc("activé").unwrap());
+        assert!(conv.convert_nfc("active").is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn from_toml_value_reads_table() {
+        let doc: toml::Value = toml::from_str(
+            r#"
+            bools = { true = "yes", false = "no" }
+            broken = { true = "yes", false = 0 }
+            "#,
+        )
+        .unwrap();
+
+        let conv = AsciiBytesToBoolOwned::from_toml_value(&doc["bools"]).unwrap();
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(!conv.convert(b"no").unwrap());
+
+        let err = AsciiBytesToBoolOwned::from_toml_value(&doc["broken"]).unwrap_err();
+        assert!(matches!(err, Str2BoolError::Malformed(_)));
+        assert!(AsciiBytesToBoolOwned::from_toml_value(&doc).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use std::io;
+
+use io::BufRead;
+
+use crate::ascii_bytes::AsciiBytesToBool;
+
+pub struct BoolTokenReader<R, C> {
+    reader: R,
+    converter: C,
+}
+
+impl<R, C> BoolTokenReader<R, C> {
+    pub fn new(reader: R, converter: C) -> Self {
+        Self { reader, converter }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, C> BoolTokenReader<R, C>
+where
+    R: BufRead,
+{
+    fn next_token(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut token: Vec<u8> = vec![];
+        loop {
+            let buf: &[u8] = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let skip: usize = if token.is_empty() {
+                buf.iter().take_while(|b| b.is_ascii_whitespace()).count()
+            } else {
+                0
+            };
+            let rest: &[u8] = &buf[skip..];
+            match rest.iter().position(u8::is_ascii_whitespace) {
+                Some(n) => {
+                    token.extend_from_slice(&rest[..n]);
+                    self.reader.consume(skip + n);
+                    if !token.is_empty() {
+                        break;
+                    }
+                }
+                None => {
+                    token.extend_from_slice(rest);
+                    let used: usize = buf.len();
+                    self.reader.consume(used);
+                }
+            }
+        }
+        Ok((!token.is_empty()).then_some(token))
+    }
+}
+
+impl<R, C> Iterator for BoolTokenReader<R, C>
+where
+    R: BufRead,
+    C: AsciiBytesToBool,
+    C::Error: Into<io::Error>,
+{
+    type Item = io::Result<bool>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_token() {
+            Ok(Some(token)) => Some(self.converter.convert(&token).map_err(Into::into)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii_bytes_set::AsciiBytesToBoolSet;
+    use std::io::{BufReader, Cursor, ErrorKind};
+
+    #[test]
+    fn reads_whitespace_separated_tokens() {
+        let reader = BoolTokenReader::new(
+            Cursor::new(b"true false yes".to_vec()),
+            AsciiBytesToBoolSet::new_english(),
+        );
+
+        let values: Vec<bool> = reader.collect::<io::Result<_>>().unwrap();
+        assert_eq!(values, vec![true, false, true]);
+    }
+
+    #[test]
+    fn reads_tokens_across_buffer_boundaries() {
+        let input: &[u8] = b"  true\n\tfalse  \r\n off  on\n";
+        let reader = BoolTokenReader::new(
+            BufReader::with_capacity(3, input),
+            AsciiBytesToBoolSet::new_english(),
+        );
+
+        let values: Vec<bool> = reader.collect::<io::Result<_>>().unwrap();
+        assert_eq!(values, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn invalid_token_yields_error() {
+        let mut reader = BoolTokenReader::new(
+            Cursor::new(b"yes maybe no".to_vec()),
+            AsciiBytesToBoolSet::new_english(),
+        );
+
+        assert!(reader.next().unwrap().unwrap());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(!reader.next().unwrap().unwrap());
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use serde::de::{self, Visitor};
+
+use crate::ascii_bytes::AsciiBytesToBool;
+
+/// A [`Visitor`] accepting strings and bytes, converted by the inner converter.
+///
+/// Use it from a custom `Deserialize` impl, e.g.
+/// `deserializer.deserialize_str(BoolVisitor::new(AsciiBytesToBoolPair::new_yes_no()))`.
+pub struct BoolVisitor<C> {
+    converter: C,
+}
+
+impl<C> BoolVisitor<C> {
+    pub fn new(converter: C) -> Self {
+        Self { converter }
+    }
+}
+
+impl<'de, C> Visitor<'de> for BoolVisitor<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a boolean token as a string or bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<bool, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<bool, E>
+    where
+        E: de::Error,
+    {
+        self.converter.convert(v).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii_bytes::AsciiBytesToBoolPair;
+    use serde::de::Deserializer;
+    use serde::de::value::{BorrowedBytesDeserializer, Error, StrDeserializer};
+
+    fn visitor() -> BoolVisitor<AsciiBytesToBoolPair> {
+        BoolVisitor::new(AsciiBytesToBoolPair::new_yes_no())
+    }
+
+    #[test]
+    fn bool_visitor_accepts_str_and_bytes() {
+        let de = StrDeserializer::<Error>::new("yes");
+        assert!(de.deserialize_str(visitor()).unwrap());
+
+        let de = BorrowedBytesDeserializer::<Error>::new(b"no");
+        assert!(!de.deserialize_bytes(visitor()).unwrap());
+
+        let de = StrDeserializer::<Error>::new("maybe");
+        let err = de.deserialize_str(visitor()).unwrap_err();
+        assert!(err.to_string().contains("Invalid boolean representation"));
+    }
+}
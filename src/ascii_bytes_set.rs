@@ -0,0 +1,269 @@
+use std::io;
+
+use io::ErrorKind;
+
+use crate::ascii_bytes::{AsciiBytesToBool, AsciiBytesToBoolPair};
+use crate::error::{INVALID_BOOLEAN_REPRESENTATION, Str2BoolError};
+
+#[derive(Debug, Clone)]
+pub struct AsciiBytesToBoolSet {
+    pub true_values: Vec<&'static [u8]>,
+    pub false_values: Vec<&'static [u8]>,
+}
+
+impl AsciiBytesToBoolSet {
+    pub fn new_english() -> Self {
+        Self {
+            true_values: vec![b"true", b"yes", b"on", b"y", b"t", b"1"],
+            false_values: vec![b"false", b"no", b"off", b"n", b"f", b"0"],
+        }
+    }
+
+    pub fn new_positive_negative() -> Self {
+        Self {
+            true_values: vec![b"positive", b"pos"],
+            false_values: vec![b"negative", b"neg"],
+        }
+    }
+
+    pub fn new_custom(true_values: Vec<&'static [u8]>, false_values: Vec<&'static [u8]>) -> Self {
+        Self {
+            true_values,
+            false_values,
+        }
+    }
+
+    pub fn from_pairs(entries: &[(&'static [u8], bool)]) -> Result<Self, Str2BoolError> {
+        Self::from_entries(entries.iter().copied())
+            .map_err(|token| Str2BoolError::Conflict(token.to_vec()))
+    }
+
+    pub fn try_build_capped(
+        entries: &[(&'static [u8], bool)],
+        max: usize,
+    ) -> Result<Self, Str2BoolError> {
+        if max < entries.len() {
+            return Err(Str2BoolError::TooManySynonyms { max });
+        }
+        Self::from_entries(entries.iter().copied())
+            .map_err(|token| Str2BoolError::Conflict(token.to_vec()))
+    }
+
+    fn from_entries<I>(entries: I) -> Result<Self, &'static [u8]>
+    where
+        I: IntoIterator<Item = (&'static [u8], bool)>,
+    {
+        let mut set = Self::new_custom(vec![], vec![]);
+        for (token, value) in entries {
+            let (same, other) = if value {
+                (&mut set.true_values, &set.false_values)
+            } else {
+                (&mut set.false_values, &set.true_values)
+            };
+            if other.contains(&token) {
+                return Err(token);
+            }
+            if !same.contains(&token) {
+                same.push(token);
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl AsciiBytesToBoolSet {
+    pub fn accepted_values(&self) -> Vec<&[u8]> {
+        self.true_values
+            .iter()
+            .chain(self.false_values.iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn synonyms(&self, value: bool) -> Vec<&[u8]> {
+        let values = if value {
+            &self.true_values
+        } else {
+            &self.false_values
+        };
+        values.to_vec()
+    }
+
+    pub fn token_diff(&self, other: &AsciiBytesToBoolSet) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        let old: Vec<&[u8]> = self.accepted_values();
+        let new: Vec<&[u8]> = other.accepted_values();
+        let added: Vec<Vec<u8>> = new
+            .iter()
+            .filter(|t| !old.contains(t))
+            .map(|t| t.to_vec())
+            .collect();
+        let removed: Vec<Vec<u8>> = old
+            .iter()
+            .filter(|t| !new.contains(t))
+            .map(|t| t.to_vec())
+            .collect();
+        (added, removed)
+    }
+}
+
+impl AsciiBytesToBool for AsciiBytesToBoolSet {
+    type Error = io::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        if self.true_values.contains(&input) {
+            Ok(true)
+        } else if self.false_values.contains(&input) {
+            Ok(false)
+        } else {
+            Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                INVALID_BOOLEAN_REPRESENTATION,
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AsciiBytesToBoolSetBuilder {
+    entries: Vec<(&'static [u8], bool)>,
+}
+
+impl AsciiBytesToBoolSetBuilder {
+    pub fn add_pair<E>(mut self, pair: AsciiBytesToBoolPair<E>) -> Self {
+        self.entries.push((pair.true_value, true));
+        self.entries.push((pair.false_value, false));
+        self
+    }
+
+    pub fn build(self) -> Result<AsciiBytesToBoolSet, Str2BoolError> {
+        AsciiBytesToBoolSet::from_entries(self.entries)
+            .map_err(|token| Str2BoolError::Conflict(token.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_set_converts_correctly() {
+        let set = AsciiBytesToBoolSet::new_english();
+
+        assert!(set.convert(b"yes").unwrap());
+        assert!(set.convert(b"1").unwrap());
+        assert!(!set.convert(b"off").unwrap());
+        assert!(!set.convert(b"n").unwrap());
+
+        let err = set.convert(b"maybe").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn positive_negative_set_accepts_abbreviations() {
+        let set = AsciiBytesToBoolSet::new_positive_negative();
+
+        assert!(set.convert(b"positive").unwrap());
+        assert!(set.convert(b"pos").unwrap());
+        assert!(!set.convert(b"negative").unwrap());
+        assert!(!set.convert(b"neg").unwrap());
+        assert!(set.convert(b"p").is_err());
+    }
+
+    #[test]
+    fn synonyms_returns_all_tokens_for_value() {
+        let set = AsciiBytesToBoolSet::new_english();
+
+        let expected: Vec<&[u8]> = vec![b"true", b"yes", b"on", b"y", b"t", b"1"];
+        assert_eq!(set.synonyms(true), expected);
+
+        let expected: Vec<&[u8]> = vec![b"false", b"no", b"off", b"n", b"f", b"0"];
+        assert_eq!(set.synonyms(false), expected);
+    }
+
+    #[test]
+    fn accepted_values_lists_true_then_false() {
+        let set = AsciiBytesToBoolSet::new_custom(vec![b"ok"], vec![b"ng"]);
+
+        let expected: Vec<&[u8]> = vec![b"ok", b"ng"];
+        assert_eq!(set.accepted_values(), expected);
+    }
+
+    #[test]
+    fn from_pairs_builds_set() {
+        let set = AsciiBytesToBoolSet::from_pairs(&[
+            (b"yes", true),
+            (b"no", false),
+            (b"on", true),
+            (b"yes", true),
+        ])
+        .unwrap();
+
+        let expected: Vec<&[u8]> = vec![b"yes", b"on"];
+        assert_eq!(set.synonyms(true), expected);
+        let expected: Vec<&[u8]> = vec![b"no"];
+        assert_eq!(set.synonyms(false), expected);
+    }
+
+    #[test]
+    fn from_pairs_rejects_conflicting_entries() {
+        let err = AsciiBytesToBoolSet::from_pairs(&[(b"yes", true), (b"yes", false)]).unwrap_err();
+        assert_eq!(err, Str2BoolError::Conflict(b"yes".to_vec()));
+        assert!(
+            err.to_string()
+                .contains("Conflicting boolean representation: yes")
+        );
+    }
+
+    #[test]
+    fn builder_merges_pairs() {
+        let set = AsciiBytesToBoolSetBuilder::default()
+            .add_pair(AsciiBytesToBoolPair::new_yes_no())
+            .add_pair(AsciiBytesToBoolPair::new_on_off())
+            .build()
+            .unwrap();
+
+        let expected: Vec<&[u8]> = vec![b"yes", b"on"];
+        assert_eq!(set.synonyms(true), expected);
+        let expected: Vec<&[u8]> = vec![b"no", b"off"];
+        assert_eq!(set.synonyms(false), expected);
+        assert!(set.convert(b"on").unwrap());
+        assert!(!set.convert(b"no").unwrap());
+    }
+
+    #[test]
+    fn builder_rejects_conflicting_pairs() {
+        let err = AsciiBytesToBoolSetBuilder::default()
+            .add_pair(AsciiBytesToBoolPair::new_y_n())
+            .add_pair(AsciiBytesToBoolPair::new_custom(b"n", b"y"))
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Str2BoolError::Conflict(b"n".to_vec()));
+    }
+
+    #[test]
+    fn try_build_capped_limits_entry_count() {
+        let entries: &[(&'static [u8], bool)] = &[(b"yes", true), (b"no", false), (b"on", true)];
+
+        let set = AsciiBytesToBoolSet::try_build_capped(entries, 3).unwrap();
+        assert!(set.convert(b"on").unwrap());
+
+        let err = AsciiBytesToBoolSet::try_build_capped(entries, 2).unwrap_err();
+        assert_eq!(err, Str2BoolError::TooManySynonyms { max: 2 });
+
+        let err =
+            AsciiBytesToBoolSet::try_build_capped(&[(b"y", true), (b"y", false)], 2).unwrap_err();
+        assert_eq!(err, Str2BoolError::Conflict(b"y".to_vec()));
+    }
+
+    #[test]
+    fn token_diff_lists_added_and_removed() {
+        let old = AsciiBytesToBoolSet::new_custom(vec![b"true", b"yes"], vec![b"false", b"no"]);
+        let new = AsciiBytesToBoolSet::new_custom(vec![b"true", b"on"], vec![b"false", b"off"]);
+
+        let (added, removed) = old.token_diff(&new);
+        assert_eq!(added, vec![b"on".to_vec(), b"off".to_vec()]);
+        assert_eq!(removed, vec![b"yes".to_vec(), b"no".to_vec()]);
+
+        assert_eq!(old.token_diff(&old), (vec![], vec![]));
+    }
+}
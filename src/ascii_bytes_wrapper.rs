@@ -0,0 +1,609 @@
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use crate::ascii_bytes::{AsciiBytesToBool, AsciiBytesToBoolPair, edit_distance};
+use crate::error::BoolParseError;
+
+pub struct RecordingConverter<C> {
+    pub inner: C,
+    last_error: RefCell<Option<String>>,
+}
+
+impl<C> RecordingConverter<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            last_error: RefCell::new(None),
+        }
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.borrow().clone()
+    }
+}
+
+impl<C> AsciiBytesToBool for RecordingConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        self.inner.convert_scored(input).inspect_err(|e| {
+            *self.last_error.borrow_mut() = Some(e.to_string());
+        })
+    }
+}
+
+/// Retries a failed conversion after stripping each of the `suffixes`.
+///
+/// The suffixes are tried in order and the first successful match wins, so a
+/// suffix which turns one token into another (e.g. `s` on a vocabulary
+/// containing both `no` and `nos`) can silently change the result.
+pub struct SuffixTolerantConverter<C> {
+    pub suffixes: &'static [&'static [u8]],
+    pub inner: C,
+}
+
+impl<C> AsciiBytesToBool for SuffixTolerantConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        let err: C::Error = match self.inner.convert_scored(input) {
+            Ok(scored) => return Ok(scored),
+            Err(e) => e,
+        };
+        self.suffixes
+            .iter()
+            .filter_map(|suffix| input.strip_suffix(*suffix))
+            .find_map(|stripped| self.inner.convert_scored(stripped).ok())
+            .ok_or(err)
+    }
+}
+
+pub struct DeprecatingConverter<C> {
+    pub deprecated: Vec<&'static [u8]>,
+    pub inner: C,
+    warnings: RefCell<Vec<String>>,
+}
+
+impl<C> DeprecatingConverter<C> {
+    pub fn new(deprecated: Vec<&'static [u8]>, inner: C) -> Self {
+        Self {
+            deprecated,
+            inner,
+            warnings: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings.take()
+    }
+}
+
+impl<C> AsciiBytesToBool for DeprecatingConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        let scored: (bool, f32) = self.inner.convert_scored(input)?;
+        if self.deprecated.contains(&input) {
+            self.warnings.borrow_mut().push(format!(
+                "Deprecated boolean representation: {}",
+                String::from_utf8_lossy(input),
+            ));
+        }
+        Ok(scored)
+    }
+}
+
+/// Removes one leading article (matched ASCII case-insensitively and followed
+/// by ASCII whitespace) before delegating to `inner`.
+///
+/// Only a single article is stripped; `the a yes` is passed on as `a yes`.
+pub struct ArticleStrippingConverter<C> {
+    pub articles: &'static [&'static [u8]],
+    pub inner: C,
+}
+
+impl<C> ArticleStrippingConverter<C> {
+    fn strip_article<'a>(&self, input: &'a [u8]) -> &'a [u8] {
+        self.articles
+            .iter()
+            .find_map(|article| {
+                let head: &[u8] = input.get(..article.len())?;
+                let rest: &[u8] = &input[article.len()..];
+                let stripped: &[u8] = rest.trim_ascii_start();
+                let separated: bool = stripped.len() < rest.len();
+                (separated && head.eq_ignore_ascii_case(article)).then_some(stripped)
+            })
+            .unwrap_or(input)
+    }
+}
+
+impl<C> AsciiBytesToBool for ArticleStrippingConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.inner.convert(self.strip_article(input))
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        self.inner.convert_scored(self.strip_article(input))
+    }
+}
+
+pub struct MinLenConverter<C> {
+    pub min: usize,
+    pub inner: C,
+}
+
+impl<C> AsciiBytesToBool for MinLenConverter<C>
+where
+    C: AsciiBytesToBool,
+    C::Error: BoolParseError,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        if input.len() < self.min {
+            return Err(C::Error::too_short(self.min));
+        }
+        self.inner.convert_scored(input)
+    }
+}
+
+pub struct RequiredConverter<C> {
+    pub inner: C,
+}
+
+impl<C> AsciiBytesToBool for RequiredConverter<C>
+where
+    C: AsciiBytesToBool,
+    C::Error: BoolParseError,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        if input.is_empty() {
+            return Err(C::Error::required());
+        }
+        self.inner.convert_scored(input)
+    }
+}
+
+pub struct OnlyTrueConverter<C> {
+    pub inner: C,
+}
+
+impl<C> AsciiBytesToBool for OnlyTrueConverter<C>
+where
+    C: AsciiBytesToBool,
+    C::Error: BoolParseError,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        match self.inner.convert_scored(input)? {
+            (true, score) => Ok((true, score)),
+            (false, _) => Err(C::Error::must_be_true(input)),
+        }
+    }
+}
+
+/// Falls back to accepting input within edit distance 1 (including a swap of
+/// two adjacent bytes) of one of the `tokens` when `inner` rejects it.
+///
+/// The fallback only runs on failure and skips tokens whose length differs
+/// from the input by more than one, so long input costs `O(tokens)`; otherwise
+/// it costs `O(len * token_len)` time and `O(token_len)` memory per token.
+/// Short tokens are prone to false positives (`y` is one edit away from any
+/// single byte), so prefer this for vocabularies of full words. Input close to
+/// both a true and a false token is rejected.
+pub struct TypoTolerantConverter<C> {
+    pub tokens: Vec<(&'static [u8], bool)>,
+    pub inner: C,
+}
+
+impl<E> TypoTolerantConverter<AsciiBytesToBoolPair<E>> {
+    pub fn from_pair(pair: AsciiBytesToBoolPair<E>) -> Self {
+        Self {
+            tokens: vec![(pair.true_value, true), (pair.false_value, false)],
+            inner: pair,
+        }
+    }
+}
+
+impl<C> TypoTolerantConverter<C> {
+    fn nearest(&self, input: &[u8]) -> Option<(bool, usize)> {
+        let mut near = self
+            .tokens
+            .iter()
+            .filter(|(token, _)| input.len().abs_diff(token.len()) <= 1)
+            .map(|(token, value)| (*value, edit_distance(input, token)))
+            .filter(|(_, distance)| *distance <= 1);
+        let first: (bool, usize) = near.next()?;
+        near.try_fold(first, |(b, d), (v, distance)| {
+            (v == b).then_some((b, d.min(distance)))
+        })
+    }
+}
+
+impl<C> AsciiBytesToBool for TypoTolerantConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    /// Scores `1 / (1 + distance)` for the nearest token, so a fallback match
+    /// one edit away scores `0.5`.
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        let err: C::Error = match self.inner.convert(input) {
+            Ok(b) => return Ok((b, 1.0)),
+            Err(e) => e,
+        };
+        match self.nearest(input) {
+            Some((b, distance)) => Ok((b, 1.0 / (1 + distance) as f32)),
+            None => Err(err),
+        }
+    }
+}
+
+/// Maps empty and all-whitespace input to false before consulting `inner`.
+///
+/// Whitespace is ASCII whitespace as in [`u8::is_ascii_whitespace`]: space,
+/// `\t`, `\n`, `\x0C` and `\r`.
+pub struct BlankIsFalse<C> {
+    pub inner: C,
+}
+
+impl BlankIsFalse<AsciiBytesToBoolPair> {
+    pub fn new_blank_is_false(tv: &'static [u8]) -> Self {
+        Self {
+            inner: AsciiBytesToBoolPair::new_from_true_value(tv),
+        }
+    }
+}
+
+impl<C> AsciiBytesToBool for BlankIsFalse<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        if input.trim_ascii().is_empty() {
+            return Ok((false, 1.0));
+        }
+        self.inner.convert_scored(input)
+    }
+}
+
+pub struct TimedConverter<C> {
+    pub inner: C,
+    total: Cell<Duration>,
+    count: Cell<u64>,
+}
+
+impl<C> TimedConverter<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            total: Cell::new(Duration::ZERO),
+            count: Cell::new(0),
+        }
+    }
+
+    /// The total time spent converting and the number of calls, successful
+    /// or not; divide the former by the latter for the average.
+    pub fn stats(&self) -> (Duration, u64) {
+        (self.total.get(), self.count.get())
+    }
+}
+
+impl<C> AsciiBytesToBool for TimedConverter<C>
+where
+    C: AsciiBytesToBool,
+{
+    type Error = C::Error;
+
+    fn convert(&self, input: &[u8]) -> Result<bool, Self::Error> {
+        self.convert_scored(input).map(|(b, _)| b)
+    }
+
+    fn convert_scored(&self, input: &[u8]) -> Result<(bool, f32), Self::Error> {
+        let started: Instant = Instant::now();
+        let result = self.inner.convert_scored(input);
+        self.total.set(self.total.get() + started.elapsed());
+        self.count.set(self.count.get() + 1);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii_bytes::AsciiBytesToBoolPair;
+    use crate::ascii_bytes_set::AsciiBytesToBoolSet;
+    use crate::error::Str2BoolError;
+
+    #[test]
+    fn recording_converter_keeps_last_error() {
+        let conv = RecordingConverter::new(AsciiBytesToBoolPair::default());
+        assert_eq!(conv.last_error(), None);
+
+        assert!(conv.convert(b"true").unwrap());
+        assert_eq!(conv.last_error(), None);
+
+        assert!(conv.convert(b"maybe").is_err());
+        let msg = conv.last_error().unwrap();
+        assert!(msg.contains("Invalid boolean representation"));
+
+        assert!(!conv.convert(b"false").unwrap());
+        assert_eq!(conv.last_error(), Some(msg));
+    }
+
+    #[test]
+    fn suffix_tolerant_converter_strips_suffixes() {
+        let conv = SuffixTolerantConverter {
+            suffixes: &[b"es", b"s"],
+            inner: AsciiBytesToBoolPair::new_yes_no(),
+        };
+
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(conv.convert(b"yeses").unwrap());
+        assert!(!conv.convert(b"nos").unwrap());
+        assert!(conv.convert(b"yesx").is_err());
+    }
+
+    #[test]
+    fn deprecating_converter_collects_warnings() {
+        let conv = DeprecatingConverter::new(vec![b"y", b"n"], AsciiBytesToBoolSet::new_english());
+
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(conv.take_warnings().is_empty());
+
+        assert!(conv.convert(b"y").unwrap());
+        assert!(!conv.convert(b"n").unwrap());
+        let warnings = conv.take_warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("Deprecated boolean representation: y"));
+        assert!(warnings[1].contains("Deprecated boolean representation: n"));
+
+        assert!(conv.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn article_stripping_converter_strips_one_article() {
+        let conv = ArticleStrippingConverter {
+            articles: &[b"the", b"a"],
+            inner: AsciiBytesToBoolPair::new_yes_no(),
+        };
+
+        assert!(conv.convert(b"the yes").unwrap());
+        assert!(conv.convert(b"The  yes").unwrap());
+        assert!(!conv.convert(b"a no").unwrap());
+        assert!(conv.convert(b"yes").unwrap());
+
+        assert!(conv.convert(b"theyes").is_err());
+        assert!(conv.convert(b"the a yes").is_err());
+    }
+
+    #[test]
+    fn min_len_converter_rejects_short_input() {
+        let conv = MinLenConverter {
+            min: 4,
+            inner: AsciiBytesToBoolSet::new_english(),
+        };
+
+        assert!(conv.convert(b"true").unwrap());
+        assert!(!conv.convert(b"false").unwrap());
+
+        let err = conv.convert(b"t").unwrap_err();
+        assert!(err.to_string().contains("too short"));
+        assert!(conv.convert(b"yes").is_err());
+    }
+
+    #[test]
+    fn required_converter_rejects_empty_input() {
+        let conv = RequiredConverter {
+            inner: AsciiBytesToBoolPair::new_o(),
+        };
+
+        assert!(conv.convert(b"o").unwrap());
+        let err = conv.convert(b"").unwrap_err();
+        assert_eq!(err.to_string(), "value is required");
+
+        let conv = RequiredConverter {
+            inner: AsciiBytesToBoolPair::default().with_error::<Str2BoolError>(),
+        };
+        assert_eq!(conv.convert(b"").unwrap_err(), Str2BoolError::Required);
+        assert_eq!(
+            conv.convert(b"maybe").unwrap_err(),
+            Str2BoolError::InvalidBytes(b"maybe".to_vec())
+        );
+    }
+
+    #[test]
+    fn only_true_converter_rejects_false() {
+        let conv = OnlyTrueConverter {
+            inner: AsciiBytesToBoolPair::new_on_off(),
+        };
+
+        assert!(conv.convert(b"on").unwrap());
+        let err = conv.convert(b"off").unwrap_err();
+        assert_eq!(err.to_string(), "value must be true");
+        let err = conv.convert(b"maybe").unwrap_err();
+        assert!(err.to_string().contains("Invalid boolean representation"));
+
+        let conv = OnlyTrueConverter {
+            inner: AsciiBytesToBoolPair::new_on_off().with_error::<Str2BoolError>(),
+        };
+        assert_eq!(
+            conv.convert(b"off").unwrap_err(),
+            Str2BoolError::MustBeTrue(b"off".to_vec())
+        );
+    }
+
+    #[test]
+    fn typo_tolerant_converter_accepts_near_tokens() {
+        let conv = TypoTolerantConverter::from_pair(AsciiBytesToBoolPair::default());
+
+        assert!(conv.convert(b"true").unwrap());
+        assert!(conv.convert(b"treu").unwrap());
+        assert!(conv.convert(b"tru").unwrap());
+        assert!(!conv.convert(b"flase").unwrap());
+        assert!(!conv.convert(b"falsee").unwrap());
+
+        assert!(conv.convert(b"maybe").is_err());
+        assert!(conv.convert(b"tr").is_err());
+    }
+
+    #[test]
+    fn typo_tolerant_converter_rejects_large_input() {
+        let conv = TypoTolerantConverter::from_pair(AsciiBytesToBoolPair::default());
+        let mut input: Vec<u8> = vec![b'x'; 4 << 20];
+        input[..4].copy_from_slice(b"true");
+
+        assert!(conv.convert(&input).is_err());
+        assert!(conv.convert_scored(&input).is_err());
+    }
+
+    #[test]
+    fn typo_tolerant_converter_rejects_ambiguous_input() {
+        let conv = TypoTolerantConverter::from_pair(AsciiBytesToBoolPair::new_on_off());
+
+        assert!(conv.convert(b"of").is_err());
+        assert!(!conv.convert(b"ofd").unwrap());
+    }
+
+    #[test]
+    fn typo_tolerant_converter_scores_fuzzy_matches_lower() {
+        let conv = TypoTolerantConverter::from_pair(AsciiBytesToBoolPair::default());
+
+        assert_eq!(conv.convert_scored(b"true").unwrap(), (true, 1.0));
+
+        let (b, score) = conv.convert_scored(b"ture").unwrap();
+        assert!(b);
+        assert!(score < 1.0);
+
+        assert!(conv.convert_scored(b"maybe").is_err());
+    }
+
+    #[test]
+    fn blank_is_false_accepts_whitespace() {
+        let conv = BlankIsFalse::new_blank_is_false(b"x");
+
+        assert!(conv.convert(b"x").unwrap());
+        assert!(!conv.convert(b"").unwrap());
+        assert!(!conv.convert(b"   ").unwrap());
+        assert!(!conv.convert(b" \t\r\n").unwrap());
+        assert!(conv.convert(b" x ").is_err());
+
+        let conv = BlankIsFalse {
+            inner: AsciiBytesToBoolPair::new_yes_no(),
+        };
+        assert!(!conv.convert(b"\t").unwrap());
+        assert!(conv.convert(b"yes").unwrap());
+        assert!(AsciiBytesToBoolPair::new_x().convert(b"   ").is_err());
+    }
+
+    #[test]
+    fn wrappers_forward_fuzzy_scores() {
+        let typo = || TypoTolerantConverter::from_pair(AsciiBytesToBoolPair::default());
+        let fuzzy = |scored: (bool, f32)| scored.0 && scored.1 < 1.0;
+
+        let conv = RecordingConverter::new(typo());
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+        assert_eq!(conv.convert_scored(b"true").unwrap(), (true, 1.0));
+
+        let conv = TimedConverter::new(typo());
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+        assert_eq!(conv.stats().1, 1);
+
+        let conv = DeprecatingConverter::new(vec![b"treu"], typo());
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+        assert_eq!(conv.take_warnings().len(), 1);
+
+        let conv = MinLenConverter {
+            min: 2,
+            inner: typo(),
+        };
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+
+        let conv = RequiredConverter { inner: typo() };
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+
+        let conv = OnlyTrueConverter { inner: typo() };
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+
+        let conv = BlankIsFalse { inner: typo() };
+        assert!(fuzzy(conv.convert_scored(b"treu").unwrap()));
+        assert_eq!(conv.convert_scored(b" ").unwrap(), (false, 1.0));
+
+        let conv = ArticleStrippingConverter {
+            articles: &[b"the"],
+            inner: typo(),
+        };
+        assert!(fuzzy(conv.convert_scored(b"the treu").unwrap()));
+
+        let conv = SuffixTolerantConverter {
+            suffixes: &[b"!"],
+            inner: typo(),
+        };
+        assert!(fuzzy(conv.convert_scored(b"treu!").unwrap()));
+    }
+
+    #[test]
+    fn timed_converter_counts_calls() {
+        let conv = TimedConverter::new(AsciiBytesToBoolPair::default());
+        assert_eq!(conv.stats(), (Duration::ZERO, 0));
+
+        assert!(conv.convert(b"true").unwrap());
+        assert!(!conv.convert(b"false").unwrap());
+        assert!(conv.convert(b"maybe").is_err());
+
+        let (_, count) = conv.stats();
+        assert_eq!(count, 3);
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::io;
+
+use io::ErrorKind;
+
+pub const INVALID_BOOLEAN_REPRESENTATION: &str = "Invalid boolean representation";
+
+pub trait BoolParseError: Sized {
+    fn invalid(input: &[u8]) -> Self;
+
+    fn invalid_char(input: char) -> Self {
+        let mut buf = [0; 4];
+        Self::invalid(input.encode_utf8(&mut buf).as_bytes())
+    }
+
+    fn too_long(_max_len: usize) -> Self {
+        Self::invalid(&[])
+    }
+
+    fn too_short(_min_len: usize) -> Self {
+        Self::invalid(&[])
+    }
+
+    fn malformed(input: &[u8]) -> Self {
+        Self::invalid(input)
+    }
+
+    fn required() -> Self {
+        Self::invalid(&[])
+    }
+
+    fn must_be_true(input: &[u8]) -> Self {
+        Self::invalid(input)
+    }
+
+    fn ambiguous(input: &[u8]) -> Self {
+        Self::invalid(input)
+    }
+}
+
+impl BoolParseError for io::Error {
+    fn invalid(_input: &[u8]) -> Self {
+        io::Error::new(ErrorKind::InvalidInput, INVALID_BOOLEAN_REPRESENTATION)
+    }
+
+    fn invalid_char(input: char) -> Self {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid boolean representation: {}", input),
+        )
+    }
+
+    fn too_long(max_len: usize) -> Self {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Boolean representation too long: max {} bytes", max_len),
+        )
+    }
+
+    fn too_short(min_len: usize) -> Self {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Boolean representation too short: min {} bytes", min_len),
+        )
+    }
+
+    fn malformed(_input: &[u8]) -> Self {
+        io::Error::new(ErrorKind::InvalidData, "Malformed boolean representation")
+    }
+
+    fn required() -> Self {
+        io::Error::new(ErrorKind::InvalidInput, "value is required")
+    }
+
+    fn must_be_true(_input: &[u8]) -> Self {
+        io::Error::new(ErrorKind::InvalidInput, "value must be true")
+    }
+
+    fn ambiguous(_input: &[u8]) -> Self {
+        io::Error::new(ErrorKind::InvalidInput, "Ambiguous boolean representation")
+    }
+}
+
+/// Structured errors for converters generic over [`BoolParseError`].
+///
+/// Opt in with `with_error::<Str2BoolError>()` on the pair, owned, prefix,
+/// length and case-style converters. `AsciiBytesToBoolSet`,
+/// `AsciiBytesToBoolMap` and `AsciiBytesToBoolNumeric` always convert with
+/// `io::Error`, so they cannot report e.g. [`Str2BoolError::NonAscii`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Str2BoolError {
+    InvalidBytes(Vec<u8>),
+    NonAscii(Vec<u8>),
+    TooLong { max_len: usize },
+    TooShort { min_len: usize },
+    Malformed(Vec<u8>),
+    Required,
+    MustBeTrue(Vec<u8>),
+    Ambiguous(Vec<u8>),
+    Conflict(Vec<u8>),
+    TooManySynonyms { max: usize },
+}
+
+impl fmt::Display for Str2BoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBytes(b) => write!(
+                f,
+                "Invalid boolean representation: {}",
+                String::from_utf8_lossy(b)
+            ),
+            Self::NonAscii(b) => write!(f, "Non-ASCII boolean representation: {:x?}", b),
+            Self::TooLong { max_len } => {
+                write!(f, "Boolean representation too long: max {} bytes", max_len)
+            }
+            Self::TooShort { min_len } => {
+                write!(f, "Boolean representation too short: min {} bytes", min_len)
+            }
+            Self::Malformed(b) => write!(
+                f,
+                "Malformed boolean representation: {}",
+                String::from_utf8_lossy(b)
+            ),
+            Self::Required => write!(f, "value is required"),
+            Self::MustBeTrue(b) => {
+                write!(f, "value must be true: {}", String::from_utf8_lossy(b))
+            }
+            Self::Ambiguous(b) => write!(
+                f,
+                "Ambiguous boolean representation: {}",
+                String::from_utf8_lossy(b)
+            ),
+            Self::Conflict(b) => write!(
+                f,
+                "Conflicting boolean representation: {}",
+                String::from_utf8_lossy(b)
+            ),
+            Self::TooManySynonyms { max } => {
+                write!(f, "Too many boolean representations: max {}", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Str2BoolError {}
+
+impl BoolParseError for Str2BoolError {
+    fn invalid(input: &[u8]) -> Self {
+        if input.is_ascii() {
+            Self::InvalidBytes(input.to_vec())
+        } else {
+            Self::NonAscii(input.to_vec())
+        }
+    }
+
+    fn too_long(max_len: usize) -> Self {
+        Self::TooLong { max_len }
+    }
+
+    fn too_short(min_len: usize) -> Self {
+        Self::TooShort { min_len }
+    }
+
+    fn malformed(input: &[u8]) -> Self {
+        Self::Malformed(input.to_vec())
+    }
+
+    fn required() -> Self {
+        Self::Required
+    }
+
+    fn must_be_true(input: &[u8]) -> Self {
+        Self::MustBeTrue(input.to_vec())
+    }
+
+    fn ambiguous(input: &[u8]) -> Self {
+        Self::Ambiguous(input.to_vec())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::BoolParseError;
+
+    #[derive(Debug)]
+    pub(crate) struct CustomError(pub(crate) Vec<u8>);
+
+    impl std::fmt::Display for CustomError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "custom: {:?}", self.0)
+        }
+    }
+
+    impl std::error::Error for CustomError {}
+
+    impl BoolParseError for CustomError {
+        fn invalid(input: &[u8]) -> Self {
+            Self(input.to_vec())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii_byte::{AsciiByteToBool, AsciiByteToBoolPair};
+    use crate::ascii_bytes::{AsciiBytesToBool, AsciiBytesToBoolPair};
+
+    #[test]
+    fn str2bool_error_distinguishes_non_ascii() {
+        let pair = AsciiBytesToBoolPair::default().with_error::<Str2BoolError>();
+
+        assert!(pair.convert(b"true").unwrap());
+        assert_eq!(
+            pair.convert(b"maybe").unwrap_err(),
+            Str2BoolError::InvalidBytes(b"maybe".to_vec())
+        );
+        assert_eq!(
+            pair.convert(b"\xff\xfe").unwrap_err(),
+            Str2BoolError::NonAscii(b"\xff\xfe".to_vec())
+        );
+    }
+
+    #[test]
+    fn str2bool_error_distinguishes_non_ascii_char() {
+        let pair = AsciiByteToBoolPair::new_yn().with_error::<Str2BoolError>();
+
+        assert_eq!(
+            pair.convert_ascii_char('z').unwrap_err(),
+            Str2BoolError::InvalidBytes(b"z".to_vec())
+        );
+        assert!(matches!(
+            pair.convert_ascii_char('\u{e9}').unwrap_err(),
+            Str2BoolError::NonAscii(_)
+        ));
+    }
+
+    #[test]
+    fn str2bool_error_messages() {
+        let pair = AsciiBytesToBoolPair::default().with_error::<Str2BoolError>();
+
+        let err = pair.convert_bounded(b"falsehood", 5).unwrap_err();
+        assert_eq!(err, Str2BoolError::TooLong { max_len: 5 });
+        assert!(err.to_string().contains("too long"));
+
+        let err = pair.convert_bracket_list(b"true").unwrap_err();
+        assert_eq!(err, Str2BoolError::Malformed(b"true".to_vec()));
+        assert!(err.to_string().contains("Malformed"));
+
+        let err = pair.convert(b"nope").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid boolean representation: nope");
+    }
+}
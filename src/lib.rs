@@ -1,3 +1,20 @@
+pub mod error;
+
 pub mod ascii_bytes;
 
+pub mod ascii_bytes_owned;
+
+pub mod ascii_bytes_set;
+
+pub mod ascii_bytes_map;
+
+pub mod ascii_bytes_numeric;
+
+pub mod ascii_bytes_wrapper;
+
+pub mod ascii_bytes_reader;
+
+#[cfg(feature = "serde")]
+pub mod ascii_bytes_serde;
+
 pub mod ascii_byte;